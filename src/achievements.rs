@@ -0,0 +1,208 @@
+use macroquad::prelude::*;
+use crate::events::GameEvent;
+use crate::statistics::Stats;
+use crate::storage;
+
+const ACHIEVEMENTS_KEY: &str = "achievements";
+// Seconds each unlock toast stays on the screen
+const TOAST_DURATION: f32 = 3f32;
+
+// Lifetime statistics some achievements are unlocked by
+#[derive(Clone, Copy)]
+enum Counter {
+    BlocksDestroyed,
+    PowerupsCollected,
+    LevelsCleared,
+}
+
+impl Counter {
+    fn count(&self, lifetime: &Stats) -> u32 {
+        match self {
+            Counter::BlocksDestroyed => lifetime.total_blocks_destroyed(),
+            Counter::PowerupsCollected => lifetime.powerups_collected,
+            Counter::LevelsCleared => lifetime.levels_cleared,
+        }
+    }
+}
+
+enum Requirement {
+    // Unlocked the moment a matching event happens
+    Event(fn(&GameEvent) -> bool),
+    // Unlocked once a lifetime statistic reaches the goal
+    Count(Counter, u32),
+}
+
+pub struct Achievement {
+    // Name in the save file, so achievements can be renamed without losing them
+    id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    requirement: Requirement,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 8] = [
+    Achievement {
+        id: "first_clear",
+        name: "First steps",
+        description: "Clear a level",
+        requirement: Requirement::Event(|event| matches!(event, GameEvent::LevelCleared { .. })),
+    },
+    Achievement {
+        id: "flawless",
+        name: "Flawless",
+        description: "Clear a level without losing a ball",
+        requirement: Requirement::Event(|event| matches!(event, GameEvent::LevelCleared { flawless: true, .. })),
+    },
+    Achievement {
+        id: "level_10",
+        name: "Veteran",
+        description: "Clear level 10",
+        requirement: Requirement::Event(|event| matches!(event, GameEvent::LevelCleared { level, .. } if *level >= 10)),
+    },
+    Achievement {
+        id: "multiball",
+        name: "Juggler",
+        description: "Have 8 balls in play at once",
+        requirement: Requirement::Event(|event| matches!(event, GameEvent::BallsInPlay(count) if *count >= 8)),
+    },
+    Achievement {
+        id: "combo",
+        name: "Combo king",
+        description: "Reach the x5 combo multiplier",
+        requirement: Requirement::Event(|event| matches!(event, GameEvent::ComboMultiplier(multiplier) if *multiplier >= 5)),
+    },
+    Achievement {
+        id: "demolition",
+        name: "Demolition",
+        description: "Destroy 1000 blocks",
+        requirement: Requirement::Count(Counter::BlocksDestroyed, 1000),
+    },
+    Achievement {
+        id: "collector",
+        name: "Collector",
+        description: "Collect 50 powerups",
+        requirement: Requirement::Count(Counter::PowerupsCollected, 50),
+    },
+    Achievement {
+        id: "marathon",
+        name: "Marathon",
+        description: "Clear 50 levels",
+        requirement: Requirement::Count(Counter::LevelsCleared, 50),
+    },
+];
+
+pub struct Achievements {
+    unlocked: Vec<&'static str>,
+    // Unlocks waiting for their toast, the first one is on the screen
+    toasts: Vec<&'static Achievement>,
+    toast_timer: f32,
+}
+
+impl Achievements {
+    pub fn load() -> Self {
+        let mut achievements = Self {
+            unlocked: Vec::new(),
+            toasts: Vec::new(),
+            toast_timer: 0f32,
+        };
+        if let Some(text) = storage::load(ACHIEVEMENTS_KEY) {
+            for (name, value) in storage::parse_entries(&text) {
+                if name == "unlocked"
+                    && let Some(achievement) = ACHIEVEMENTS.iter().find(|achievement| achievement.id == value)
+                {
+                    achievements.unlocked.push(achievement.id);
+                }
+            }
+        }
+        achievements
+    }
+
+    pub fn save(&self) {
+        let text: String = self.unlocked.iter().map(|id| format!("unlocked={}\n", id)).collect();
+        storage::save(ACHIEVEMENTS_KEY, &text);
+    }
+
+    pub fn is_unlocked(&self, achievement: &Achievement) -> bool {
+        self.unlocked.contains(&achievement.id)
+    }
+
+    // Unlocks whatever the event earned, given the lifetime statistics with the event already counted.
+    // Returns true if something was unlocked, so it can be saved straight away.
+    pub fn handle(&mut self, event: &GameEvent, lifetime: &Stats) -> bool {
+        let mut unlocked_any = false;
+        for achievement in ACHIEVEMENTS.iter() {
+            if self.is_unlocked(achievement) {
+                continue;
+            }
+            let earned = match achievement.requirement {
+                Requirement::Event(matches) => matches(event),
+                Requirement::Count(counter, goal) => counter.count(lifetime) >= goal,
+            };
+            if earned {
+                self.unlocked.push(achievement.id);
+                self.toasts.push(achievement);
+                unlocked_any = true;
+            }
+        }
+        unlocked_any
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        if self.toasts.is_empty() {
+            return;
+        }
+        self.toast_timer += dt;
+        if self.toast_timer >= TOAST_DURATION {
+            self.toast_timer = 0f32;
+            self.toasts.remove(0);
+        }
+    }
+
+    pub fn draw_toast(&self, font: &Font) {
+        let Some(achievement) = self.toasts.first() else {
+            return;
+        };
+        let text = format!("Achievement unlocked: {}", achievement.name);
+        let dims = measure_text(&text, Some(font), 24u16, 1.0f32);
+        let rect = Rect::new(screen_width() * 0.5f32 - dims.width * 0.5f32 - 16f32, 80f32, dims.width + 32f32, 40f32);
+        draw_rectangle(rect.x, rect.y, rect.w, rect.h, Color::new(0.0, 0.0, 0.0, 0.7));
+        draw_text_ex(
+            &text,
+            rect.x + 16f32,
+            rect.y + rect.h * 0.5f32 + dims.offset_y * 0.5f32,
+            TextParams { font: Some(font), font_size: 24u16, color: GOLD, ..Default::default() },
+        );
+    }
+
+    pub fn draw_gallery(&self, font: &Font, lifetime: &Stats) {
+        let title = format!("Achievements {} / {}", self.unlocked.len(), ACHIEVEMENTS.len());
+        let title_dims = measure_text(&title, Some(font), 50u16, 1.0f32);
+        draw_text_ex(
+            &title,
+            screen_width() * 0.5f32 - title_dims.width * 0.5f32,
+            screen_height() * 0.15f32,
+            TextParams { font: Some(font), font_size: 50u16, color: BLACK, ..Default::default() },
+        );
+
+        let row_height = (screen_height() * 0.65f32 / ACHIEVEMENTS.len() as f32).min(50f32);
+        let font_size = (row_height * 0.5f32) as u16;
+        for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+            let y = screen_height() * 0.25f32 + i as f32 * row_height;
+            let unlocked = self.is_unlocked(achievement);
+            let color = if unlocked { BLACK } else { Color::new(0.2, 0.2, 0.2, 0.6) };
+            let status = match achievement.requirement {
+                _ if unlocked => "Unlocked".to_string(),
+                Requirement::Count(counter, goal) => format!("{} / {}", counter.count(lifetime).min(goal), goal),
+                Requirement::Event(_) => "Locked".to_string(),
+            };
+            for (cell, column) in [(achievement.name, 0.1f32), (achievement.description, 0.32f32), (status.as_str(), 0.75f32)] {
+                draw_text_ex(
+                    cell,
+                    screen_width() * column,
+                    y,
+                    TextParams { font: Some(font), font_size, color, ..Default::default() },
+                );
+            }
+        }
+    }
+}
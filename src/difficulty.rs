@@ -0,0 +1,84 @@
+use crate::game_objects::{ball::BALL_SPEED, collider::SurfaceMaterial, player::{PaddlePhysics, PLAYER_SIZE}};
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Expert];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL.iter().copied().find(|difficulty| difficulty.label() == name)
+    }
+
+    pub fn starting_lives(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 3,
+            Difficulty::Hard => 2,
+            Difficulty::Expert => 1,
+        }
+    }
+
+    pub fn ball_speed(&self) -> f32 {
+        match self {
+            Difficulty::Easy => BALL_SPEED * 0.8,
+            Difficulty::Normal => BALL_SPEED,
+            Difficulty::Hard => BALL_SPEED * 1.2,
+            Difficulty::Expert => BALL_SPEED * 1.4,
+        }
+    }
+
+    pub fn paddle_width(&self) -> f32 {
+        match self {
+            Difficulty::Easy => PLAYER_SIZE.x * 1.25,
+            Difficulty::Normal => PLAYER_SIZE.x,
+            Difficulty::Hard => PLAYER_SIZE.x * 0.85,
+            Difficulty::Expert => PLAYER_SIZE.x * 0.7,
+        }
+    }
+
+    // Chance that a block without a powerup of its own still drops one when destroyed
+    pub fn powerup_drop_chance(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.1,
+            Difficulty::Normal => 0.05,
+            Difficulty::Hard => 0.02,
+            Difficulty::Expert => 0.0,
+        }
+    }
+
+    // How sharply the paddle sends the ball off to the side, harder presets give less control over it
+    pub fn paddle_steering(&self) -> f32 {
+        match self {
+            Difficulty::Easy => SurfaceMaterial::PADDLE.steering * 0.8,
+            Difficulty::Normal => SurfaceMaterial::PADDLE.steering,
+            Difficulty::Hard => SurfaceMaterial::PADDLE.steering * 1.2,
+            Difficulty::Expert => SurfaceMaterial::PADDLE.steering * 1.4,
+        }
+    }
+
+    // Harder presets give the paddle inertia to fight and let its motion spin the ball further
+    pub fn paddle_physics(&self) -> PaddlePhysics {
+        let physics = PaddlePhysics::new();
+        match self {
+            Difficulty::Easy => PaddlePhysics { spin_factor: 0.3, ..physics },
+            Difficulty::Normal => physics,
+            Difficulty::Hard => PaddlePhysics { inertia: true, spin_factor: 0.5, ..physics },
+            Difficulty::Expert => PaddlePhysics { inertia: true, acceleration: 3000.0, friction: 2000.0, spin_factor: 0.6 },
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::game_objects::block::BlockType;
+
+// Things that happened during play, collected each frame for whatever keeps track of them
+pub enum GameEvent {
+    BlockDestroyed(BlockType),
+    PaddleHit,
+    BallLost,
+    PowerupCollected,
+    // Number of balls in play right after new ones were spawned
+    BallsInPlay(usize),
+    ComboMultiplier(i32),
+    // A level was cleared, `flawless` if no ball was lost on it
+    LevelCleared { level: usize, flawless: bool },
+}
@@ -0,0 +1,16 @@
+#[cfg(target_arch = "wasm32")]
+unsafe extern "C" {
+    fn rustanoid_has_focus() -> i32;
+}
+
+// True if the browser tab was hidden or lost focus
+#[cfg(target_arch = "wasm32")]
+pub fn lost_focus() -> bool {
+    unsafe { rustanoid_has_focus() == 0 }
+}
+
+// Macroquad doesn't report focus changes of native windows, so only the WASM build pauses by itself
+#[cfg(not(target_arch = "wasm32"))]
+pub fn lost_focus() -> bool {
+    false
+}
@@ -0,0 +1,64 @@
+// Points a versus player loses for every ball that gets past their paddle
+pub const VERSUS_MISS_PENALTY: i32 = 50;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum GameMode {
+    Classic,
+    // Single player against the clock, lost balls cost time instead of lives
+    TimeAttack,
+    // Single player on one endless board that creeps down towards the paddle
+    Survival,
+    // Hand made boards to clear with a limited number of aimed launches
+    Puzzle,
+    Coop,
+    // One paddle at the bottom and one at the top competing over the same board
+    Versus,
+    // Two players taking turns, each with their own board, score and lives
+    Alternating,
+}
+
+impl GameMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "1 Player",
+            GameMode::TimeAttack => "Time attack",
+            GameMode::Survival => "Survival",
+            GameMode::Puzzle => "Puzzle",
+            GameMode::Coop => "2 Player Co-op",
+            GameMode::Versus => "2 Player Versus",
+            GameMode::Alternating => "2 Player Alternating",
+        }
+    }
+
+    // Paddles on the screen at the same time
+    pub fn player_count(&self) -> usize {
+        match self {
+            GameMode::Classic | GameMode::TimeAttack | GameMode::Survival | GameMode::Puzzle | GameMode::Alternating => 1,
+            GameMode::Coop | GameMode::Versus => 2,
+        }
+    }
+
+    // Players taking turns on the paddles, swapping whenever a life is lost
+    pub fn turn_count(&self) -> usize {
+        match self {
+            GameMode::Alternating => 2,
+            _ => 1,
+        }
+    }
+
+    // Only single player runs can be saved and continued later
+    pub fn saves_runs(&self) -> bool {
+        *self == GameMode::Classic
+    }
+
+    // Versus players only lose points, time attack players only lose time and puzzle players only lose
+    // the launch when a ball gets past them
+    pub fn has_lives(&self) -> bool {
+        !matches!(self, GameMode::Versus | GameMode::TimeAttack | GameMode::Puzzle)
+    }
+
+    // Whether balls bounce off the top of the screen; in versus the top belongs to the second paddle
+    pub fn has_ceiling(&self) -> bool {
+        *self != GameMode::Versus
+    }
+}
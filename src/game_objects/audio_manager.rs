@@ -1,4 +1,4 @@
-use macroquad::audio::{load_sound, Sound, play_sound, PlaySoundParams};
+use macroquad::audio::{load_sound, Sound, play_sound, set_sound_volume, PlaySoundParams};
 
 pub struct AudioManager {
     pub paddle_hit: Option<Sound>,
@@ -8,7 +8,12 @@ pub struct AudioManager {
     pub level_completed: Option<Sound>,
     pub powerup_collected: Option<Sound>,
     pub wall_hit: Option<Sound>,
+    pub laser_shot: Option<Sound>,
+    pub barrier_break: Option<Sound>,
+    pub extra_life: Option<Sound>,
     pub background_music: Option<Sound>,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
 }
 
 impl AudioManager {
@@ -21,7 +26,12 @@ impl AudioManager {
             level_completed: None,
             powerup_collected: None,
             wall_hit: None,
+            laser_shot: None,
+            barrier_break: None,
+            extra_life: None,
             background_music: None,
+            sfx_volume: 1.0,
+            music_volume: 0.5,
         }
     }
 
@@ -33,6 +43,9 @@ impl AudioManager {
         self.level_completed = Some(load_sound(&format!("{}sounds/level_complete.wav", base_path)).await.unwrap());
         self.powerup_collected = Some(load_sound(&format!("{}sounds/paddle_grow.wav", base_path)).await.unwrap());
         self.wall_hit = Some(load_sound(&format!("{}sounds/wall_hit.wav", base_path)).await.unwrap());
+        self.laser_shot = Some(load_sound(&format!("{}sounds/laser_shot.wav", base_path)).await.unwrap());
+        self.barrier_break = Some(load_sound(&format!("{}sounds/barrier_break.wav", base_path)).await.unwrap());
+        self.extra_life = Some(load_sound(&format!("{}sounds/extra_life.wav", base_path)).await.unwrap());
         self.background_music = Some(load_sound(&format!("{}sounds/background_music.ogg", base_path)).await.unwrap());
     }
 
@@ -45,6 +58,9 @@ impl AudioManager {
             "level_completed" => self.play_level_completed(),
             "powerup_collected" => self.play_powerup_collected(),
             "wall_hit" => self.play_wall_hit(),
+            "laser_shot" => self.play_laser_shot(),
+            "barrier_break" => self.play_barrier_break(),
+            "extra_life" => self.play_extra_life(),
             _ => {}
         }
     }
@@ -53,7 +69,7 @@ impl AudioManager {
         if let Some(sound) = &self.paddle_hit {
             play_sound(&sound, PlaySoundParams {
                 looped: false,
-                volume: 1.0,
+                volume: self.sfx_volume,
             });
         }
     }
@@ -62,7 +78,7 @@ impl AudioManager {
         if let Some(sound) = &self.brick_hit {
             play_sound(&sound, PlaySoundParams {
                 looped: false,
-                volume: 1.0,
+                volume: self.sfx_volume,
             });
         }
     }
@@ -71,7 +87,7 @@ impl AudioManager {
         if let Some(sound) = &self.brick_destroyed {
             play_sound(&sound, PlaySoundParams {
                 looped: false,
-                volume: 1.0,
+                volume: self.sfx_volume,
             });
         }
     }
@@ -80,7 +96,7 @@ impl AudioManager {
         if let Some(sound) = &self.life_lost {
             play_sound(&sound, PlaySoundParams {
                 looped: false,
-                volume: 1.0,
+                volume: self.sfx_volume,
             });
         }
     }
@@ -89,7 +105,7 @@ impl AudioManager {
         if let Some(sound) = &self.level_completed {
             play_sound(&sound, PlaySoundParams {
                 looped: false,
-                volume: 1.0,
+                volume: self.sfx_volume,
             });
         }
     }
@@ -98,7 +114,7 @@ impl AudioManager {
         if let Some(sound) = &self.powerup_collected {
             play_sound(&sound, PlaySoundParams {
                 looped: false,
-                volume: 1.0,
+                volume: self.sfx_volume,
             });
         }
     }
@@ -107,7 +123,34 @@ impl AudioManager {
         if let Some(sound) = &self.wall_hit {
             play_sound(&sound, PlaySoundParams {
                 looped: false,
-                volume: 1.0,
+                volume: self.sfx_volume,
+            });
+        }
+    }
+
+    pub fn play_laser_shot(&self) {
+        if let Some(sound) = &self.laser_shot {
+            play_sound(sound, PlaySoundParams {
+                looped: false,
+                volume: self.sfx_volume,
+            });
+        }
+    }
+
+    pub fn play_barrier_break(&self) {
+        if let Some(sound) = &self.barrier_break {
+            play_sound(sound, PlaySoundParams {
+                looped: false,
+                volume: self.sfx_volume,
+            });
+        }
+    }
+
+    pub fn play_extra_life(&self) {
+        if let Some(sound) = &self.extra_life {
+            play_sound(sound, PlaySoundParams {
+                looped: false,
+                volume: self.sfx_volume,
             });
         }
     }
@@ -116,8 +159,16 @@ impl AudioManager {
         if let Some(music) = &self.background_music {
             play_sound(&music, PlaySoundParams {
                 looped: true,
-                volume: 0.5,
+                volume: self.music_volume,
             });
         }
     }
+
+    // Also changes the volume of the music that is already playing
+    pub fn set_music_volume(&mut self, volume: f32) {
+        self.music_volume = volume;
+        if let Some(music) = &self.background_music {
+            set_sound_volume(music, volume);
+        }
+    }
 }
\ No newline at end of file
@@ -3,56 +3,151 @@ use crate::game_objects::texture_manager::TextureManager;
 
 pub const BALL_SIZE: f32 = 50f32;
 pub const BALL_SPEED: f32 = 400f32;
+pub const MAX_BALL_SPEED: f32 = 700f32;
+// Speed per second a sped up ball loses until it is back to its base speed
+pub const BALL_SPEED_RECOVERY: f32 = 40f32;
+pub const LAUNCH_MAX_ANGLE: f32 = 60f32;
+pub const FIREBALL_DURATION: f32 = 8f32;
+// Minimum y component of the direction to avoid near horizontal angles
+pub const MIN_Y_VELOCITY: f32 = 0.5f32;
 
 pub struct Ball {
     pub rect: Rect,
     pub vel: Vec2,
+    pub speed: f32,
+    // Speed the ball settles back to after speed ups, BALL_SPEED unless the difficulty changes it
+    pub base_speed: f32,
+    // Player whose paddle last touched the ball, they get the points it scores
+    pub owner: usize,
+    // Horizontal offset from the paddle's left edge while the ball rests on it
+    pub stuck_offset: Option<f32>,
+    pub fireball_timer: f32,
 }
 
 impl Ball {
     pub fn new(pos: Vec2) -> Self {
-        let random_angle = rand::gen_range(-45f32, 45f32).to_radians();
-        let direction = vec2(random_angle.sin(), -random_angle.cos());
-        
+        Self::aimed(pos, rand::gen_range(-45f32, 45f32))
+    }
+
+    // A ball heading up at `angle` degrees from straight up, for when nothing may be left to chance
+    pub fn aimed(pos: Vec2, angle: f32) -> Self {
+        let angle = angle.to_radians();
+        let direction = vec2(angle.sin(), -angle.cos());
+
         Self {
             rect: Rect::new(pos.x, pos.y, BALL_SIZE, BALL_SIZE),
             vel: direction.normalize(),
+            speed: BALL_SPEED,
+            base_speed: BALL_SPEED,
+            owner: 0,
+            stuck_offset: None,
+            fireball_timer: 0f32,
+        }
+    }
+
+    // `facing` is the vertical direction the paddle plays towards, -1 for a paddle at the bottom
+    pub fn new_on_paddle(paddle: &Rect, facing: f32, owner: usize) -> Self {
+        let mut ball = Self::new(vec2(paddle.x, paddle.y - BALL_SIZE));
+        ball.owner = owner;
+        ball.stuck_offset = Some(paddle.w * 0.5f32 - BALL_SIZE * 0.5f32);
+        ball.follow_paddle(paddle, facing);
+        ball
+    }
+
+    pub fn with_base_speed(mut self, base_speed: f32) -> Self {
+        self.speed = base_speed;
+        self.base_speed = base_speed;
+        self
+    }
+
+    pub fn is_fireball(&self) -> bool {
+        self.fireball_timer > 0f32
+    }
+
+    pub fn is_stuck(&self) -> bool {
+        self.stuck_offset.is_some()
+    }
+
+    pub fn catch(&mut self, paddle: &Rect, facing: f32) {
+        self.stuck_offset = Some(self.rect.x - paddle.x);
+        self.follow_paddle(paddle, facing);
+    }
+
+    // Keeps a held ball resting on the playing side of the paddle
+    pub fn follow_paddle(&mut self, paddle: &Rect, facing: f32) {
+        if let Some(offset) = self.stuck_offset {
+            let offset = offset.clamp(-self.rect.w * 0.5f32, paddle.w - self.rect.w * 0.5f32);
+            self.stuck_offset = Some(offset);
+            self.rect.x = paddle.x + offset;
+            self.rect.y = if facing < 0f32 { paddle.y - self.rect.h } else { paddle.y + paddle.h };
+        }
+    }
+
+    // Releases the ball, aiming it by where it sits on the paddle and sending it off with the
+    // paddle's spin, so a serve from the centre can still be angled by moving while launching
+    pub fn launch(&mut self, paddle: &Rect, facing: f32, spin: f32) {
+        if !self.is_stuck() {
+            return;
+        }
+        let paddle_center = paddle.x + paddle.w * 0.5f32;
+        let ball_center = self.rect.x + self.rect.w * 0.5f32;
+        let relative_pos = ((ball_center - paddle_center) / (paddle.w * 0.5f32)).clamp(-1f32, 1f32);
+        self.launch_at(relative_pos * LAUNCH_MAX_ANGLE, facing);
+        self.add_spin(spin);
+    }
+
+    // Releases the ball at `angle` degrees from the paddle's playing direction
+    pub fn launch_at(&mut self, angle: f32, facing: f32) {
+        if self.stuck_offset.take().is_none() {
+            return;
+        }
+        let angle = angle.to_radians();
+        self.vel = vec2(angle.sin(), angle.cos() * facing);
+    }
+
+    // Adds English to the ball's direction, keeping it from going too flat
+    pub fn add_spin(&mut self, spin: f32) {
+        self.vel.x += spin;
+        self.vel = self.vel.normalize();
+        if self.vel.y.abs() < MIN_Y_VELOCITY {
+            self.vel.y = self.vel.y.signum() * MIN_Y_VELOCITY;
+            self.vel = self.vel.normalize();
         }
     }
 
     pub fn update(&mut self, dt: f32) {
+        self.fireball_timer = (self.fireball_timer - dt).max(0f32);
+
+        if self.is_stuck() {
+            return;
+        }
+
         // Cap the maximum delta time to prevent large jumps
         let capped_dt = dt.min(1.0 / 60.0);
         
         // Update position
-        self.rect.x += self.vel.x * capped_dt * BALL_SPEED;
-        self.rect.y += self.vel.y * capped_dt * BALL_SPEED;
+        self.rect.x += self.vel.x * capped_dt * self.speed;
+        self.rect.y += self.vel.y * capped_dt * self.speed;
 
-        // Handle screen bounds with proper reflection
-        if self.rect.x < 0f32 {
-            self.rect.x = 0f32;
-            self.vel.x = -self.vel.x;
-        }
-        if self.rect.x > screen_width() - self.rect.w {
-            self.rect.x = screen_width() - self.rect.w;
-            self.vel.x = -self.vel.x;
+        // Ease back to the normal speed after speed ups
+        if self.speed > self.base_speed {
+            self.speed = (self.speed - BALL_SPEED_RECOVERY * capped_dt).max(self.base_speed);
         }
-        if self.rect.y < 0f32 {
-            self.rect.y = 0f32;
-            self.vel.y = -self.vel.y;
-        }
-        
+
+        // Walls are colliders now, see collider::wall_colliders
         // Ensure velocity stays normalized
         self.vel = self.vel.normalize();
     }
 
     pub fn draw(&self, texture_manager: &TextureManager) {
+        let color = if self.is_fireball() { ORANGE } else { WHITE };
+
         if let Some(texture) = &texture_manager.ball_texture {
             draw_texture_ex(
                 &texture,
                 self.rect.x,
                 self.rect.y,
-                WHITE,
+                color,
                 DrawTextureParams {
                     dest_size: Some(vec2(self.rect.w, self.rect.h)),
                     ..Default::default()
@@ -0,0 +1,63 @@
+use macroquad::prelude::*;
+use crate::game_objects::particle::Particle;
+
+pub const BARRIER_HEIGHT: f32 = 8f32;
+pub const BARRIER_DURATION: f32 = 20f32;
+
+// Safety net along the edge of the playfield behind a paddle that saves a single ball
+pub struct Barrier {
+    pub rect: Rect,
+    pub timer: f32,
+    // Vertical direction balls are sent back in, the same way the protected paddle faces
+    pub facing: f32,
+}
+
+impl Barrier {
+    pub fn new(facing: f32) -> Self {
+        let y = if facing < 0f32 { screen_height() - 20f32 } else { 20f32 - BARRIER_HEIGHT };
+        Self {
+            rect: Rect::new(0f32, y, screen_width(), BARRIER_HEIGHT),
+            timer: BARRIER_DURATION,
+            facing,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.timer -= dt;
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.timer <= 0f32
+    }
+
+    // Bounces the ball back into play if it is heading into the barrier
+    pub fn bounce(&self, ball_rect: &mut Rect, vel: &mut Vec2) -> bool {
+        if vel.y * self.facing >= 0f32 || !ball_rect.overlaps(&self.rect) {
+            return false;
+        }
+        ball_rect.y = if self.facing < 0f32 { self.rect.y - ball_rect.h } else { self.rect.y + self.rect.h };
+        vel.y = vel.y.abs() * self.facing;
+        true
+    }
+
+    // Breaks the barrier into fragments that fall off the screen
+    pub fn shatter(&self) -> Vec<Particle> {
+        let fragment_count = (self.rect.w / 12f32) as usize;
+        (0..fragment_count)
+            .map(|i| {
+                let pos = vec2(self.rect.x + i as f32 * 12f32, self.rect.y);
+                let vel = vec2(rand::gen_range(-120f32, 120f32), rand::gen_range(-260f32, -80f32));
+                Particle::new(pos, vel, SKYBLUE, rand::gen_range(4f32, 9f32), rand::gen_range(0.5f32, 1f32))
+            })
+            .collect()
+    }
+
+    pub fn draw(&self) {
+        // Blink during the last few seconds before it runs out
+        if self.timer < 3f32 && (self.timer * 6f32) as i32 % 2 == 0 {
+            return;
+        }
+        draw_rectangle(self.rect.x, self.rect.y, self.rect.w, self.rect.h, SKYBLUE);
+        draw_line(self.rect.x, self.rect.y, self.rect.x + self.rect.w, self.rect.y, 2f32, WHITE);
+    }
+}
\ No newline at end of file
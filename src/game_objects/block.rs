@@ -1,17 +1,37 @@
 use macroquad::prelude::*;
+use crate::game_objects::collider::{Collider, ColliderKind, SurfaceMaterial};
 use crate::game_objects::texture_manager::TextureManager;
 
 pub const BLOCK_SIZE: Vec2 = vec2(100f32, 40f32);
 
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub enum BlockType {
     Regular,
     SpawnBallOnDeath,
     Medium,  // 2 lives
     Strong,  // 3 lives
     SpawnPowerup,
+    Indestructible,
+    SpeedPad, // indestructible, speeds the ball up
 }
 
+impl BlockType {
+    pub const ALL: [BlockType; 7] = [
+        BlockType::Regular,
+        BlockType::SpawnBallOnDeath,
+        BlockType::Medium,
+        BlockType::Strong,
+        BlockType::SpawnPowerup,
+        BlockType::Indestructible,
+        BlockType::SpeedPad,
+    ];
+
+    pub fn parse(name: &str) -> Option<BlockType> {
+        BlockType::ALL.iter().copied().find(|block_type| format!("{:?}", block_type) == name)
+    }
+}
+
+#[derive(Clone)]
 pub struct Block {
     pub rect: Rect,
     pub lives: i32,
@@ -32,8 +52,37 @@ impl Block {
         }
     }
 
-    pub fn draw(&self, texture_manager: &TextureManager) {
-        let color = match self.block_type {
+    pub fn is_destructible(&self) -> bool {
+        !matches!(self.block_type, BlockType::Indestructible | BlockType::SpeedPad)
+    }
+
+    pub fn collider(&self) -> Collider {
+        match self.block_type {
+            BlockType::SpeedPad => Collider::new(self.rect, ColliderKind::SpeedPad, SurfaceMaterial::SPEED_PAD),
+            // Deflects the ball like a piece of wall, and sounds like one
+            BlockType::Indestructible => Collider::new(self.rect, ColliderKind::Wall, SurfaceMaterial::PLAIN),
+            _ => Collider::new(self.rect, ColliderKind::Block, SurfaceMaterial::PLAIN),
+        }
+    }
+
+    // Okabe-Ito based colours that stay distinct with the common kinds of colour blindness
+    fn colorblind_color(&self) -> Color {
+        match self.block_type {
+            BlockType::Regular => WHITE,
+            BlockType::Medium | BlockType::Strong => match self.lives {
+                3 => Color::from_rgba(213, 94, 0, 255),
+                2 => Color::from_rgba(230, 159, 0, 255),
+                _ => Color::from_rgba(240, 228, 66, 255),
+            },
+            BlockType::SpawnBallOnDeath => Color::from_rgba(0, 158, 115, 255),
+            BlockType::SpawnPowerup => Color::from_rgba(0, 114, 178, 255),
+            BlockType::Indestructible => DARKGRAY,
+            BlockType::SpeedPad => Color::from_rgba(204, 121, 167, 255),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self.block_type {
             BlockType::Regular => WHITE,
             BlockType::Medium => match self.lives {
                 2 => ORANGE,
@@ -48,7 +97,13 @@ impl Block {
             },
             BlockType::SpawnBallOnDeath => GREEN,
             BlockType::SpawnPowerup => BLUE,
-        };
+            BlockType::Indestructible => DARKGRAY,
+            BlockType::SpeedPad => GOLD,
+        }
+    }
+
+    pub fn draw(&self, texture_manager: &TextureManager) {
+        let color = if texture_manager.colorblind_mode { self.colorblind_color() } else { self.color() };
         
         if let Some(texture) = &texture_manager.block_texture {
             draw_texture_ex(
@@ -62,5 +117,13 @@ impl Block {
                 },
             );
         }
+
+        // Blocks that take several hits show the hits left as pips, so they don't depend on colour
+        if texture_manager.colorblind_mode && self.lives > 1 {
+            for pip in 0..self.lives {
+                let x = self.rect.center().x + (pip as f32 - (self.lives - 1) as f32 * 0.5f32) * 12f32;
+                draw_circle(x, self.rect.center().y, 4f32, BLACK);
+            }
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,70 @@
+use macroquad::prelude::*;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum ColliderKind {
+    Paddle,
+    Block,
+    Wall,
+    SpeedPad,
+}
+
+// How a surface bounces the ball
+#[derive(Clone, Copy)]
+pub struct SurfaceMaterial {
+    // How far the hit position along the surface steers the outgoing angle, 0 for a mirror bounce
+    pub steering: f32,
+    // Shape of the steering curve: 1 is linear, higher values keep hits near the centre straighter
+    pub steering_curve: f32,
+    // Multiplier applied to the ball's speed on every bounce
+    pub speed_multiplier: f32,
+}
+
+impl SurfaceMaterial {
+    pub const PLAIN: SurfaceMaterial = SurfaceMaterial {
+        steering: 0f32,
+        steering_curve: 1f32,
+        speed_multiplier: 1f32,
+    };
+
+    pub const PADDLE: SurfaceMaterial = SurfaceMaterial {
+        steering: 1.5f32,
+        ..SurfaceMaterial::PLAIN
+    };
+
+    pub const SPEED_PAD: SurfaceMaterial = SurfaceMaterial {
+        speed_multiplier: 1.25f32,
+        ..SurfaceMaterial::PLAIN
+    };
+
+    // Horizontal push for a hit at `relative_hit_pos` (-1 left edge, 1 right edge)
+    pub fn steer(&self, relative_hit_pos: f32) -> f32 {
+        let pos = relative_hit_pos.clamp(-1f32, 1f32);
+        pos.signum() * pos.abs().powf(self.steering_curve) * self.steering
+    }
+}
+
+pub struct Collider {
+    pub rect: Rect,
+    pub kind: ColliderKind,
+    pub material: SurfaceMaterial,
+}
+
+impl Collider {
+    pub fn new(rect: Rect, kind: ColliderKind, material: SurfaceMaterial) -> Self {
+        Self { rect, kind, material }
+    }
+}
+
+// Left and right edges of the playfield plus the ceiling if the mode has one,
+// thick enough that a fast ball can't tunnel through
+pub fn wall_colliders(ceiling: bool) -> Vec<Collider> {
+    let thickness = 1000f32;
+    let mut walls = vec![
+        Collider::new(Rect::new(-thickness, -thickness, thickness, screen_height() + 2f32 * thickness), ColliderKind::Wall, SurfaceMaterial::PLAIN),
+        Collider::new(Rect::new(screen_width(), -thickness, thickness, screen_height() + 2f32 * thickness), ColliderKind::Wall, SurfaceMaterial::PLAIN),
+    ];
+    if ceiling {
+        walls.push(Collider::new(Rect::new(0f32, -thickness, screen_width(), thickness), ColliderKind::Wall, SurfaceMaterial::PLAIN));
+    }
+    walls
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use macroquad::prelude::*;
+
+pub const LASER_SIZE: Vec2 = Vec2::from_array([6f32, 20f32]);
+pub const LASER_SPEED: f32 = 900f32;
+
+pub struct Laser {
+    pub rect: Rect,
+    pub owner: usize,
+    // Vertical direction of travel, -1 for up
+    pub dir: f32,
+}
+
+impl Laser {
+    pub fn new(pos: Vec2, owner: usize, dir: f32) -> Self {
+        let y = if dir < 0f32 { pos.y - LASER_SIZE.y } else { pos.y };
+        Self {
+            rect: Rect::new(pos.x - LASER_SIZE.x * 0.5f32, y, LASER_SIZE.x, LASER_SIZE.y),
+            owner,
+            dir,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.rect.y += self.dir * dt * LASER_SPEED;
+    }
+
+    pub fn is_offscreen(&self) -> bool {
+        self.rect.y + self.rect.h < 0f32 || self.rect.y > screen_height()
+    }
+
+    pub fn draw(&self) {
+        draw_rectangle(self.rect.x, self.rect.y, self.rect.w, self.rect.h, RED);
+        draw_rectangle(self.rect.x + 1f32, self.rect.y + 2f32, self.rect.w - 2f32, self.rect.h - 4f32, YELLOW);
+    }
+}
\ No newline at end of file
@@ -1,5 +1,9 @@
 pub mod ball;
+pub mod barrier;
 pub mod block;
+pub mod collider;
+pub mod laser;
+pub mod particle;
 pub mod player;
 pub mod powerup;
 pub mod texture_manager;
@@ -0,0 +1,41 @@
+use macroquad::prelude::*;
+
+pub const PARTICLE_GRAVITY: f32 = 600f32;
+
+pub struct Particle {
+    pub pos: Vec2,
+    pub vel: Vec2,
+    pub color: Color,
+    pub size: f32,
+    pub life: f32,
+    pub max_life: f32,
+}
+
+impl Particle {
+    pub fn new(pos: Vec2, vel: Vec2, color: Color, size: f32, life: f32) -> Self {
+        Self {
+            pos,
+            vel,
+            color,
+            size,
+            life,
+            max_life: life,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.vel.y += PARTICLE_GRAVITY * dt;
+        self.pos += self.vel * dt;
+        self.life -= dt;
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.life > 0f32
+    }
+
+    pub fn draw(&self) {
+        let mut color = self.color;
+        color.a *= (self.life / self.max_life).clamp(0f32, 1f32);
+        draw_rectangle(self.pos.x, self.pos.y, self.size, self.size, color);
+    }
+}
\ No newline at end of file
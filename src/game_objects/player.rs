@@ -1,55 +1,207 @@
 use macroquad::prelude::*;
+use crate::game_objects::collider::{Collider, ColliderKind, SurfaceMaterial};
 use crate::game_objects::texture_manager::TextureManager;
+use crate::input_source::PaddleIntent;
 
 pub const PLAYER_SIZE: Vec2 = Vec2::from_array([150f32, 40f32]);
 pub const PLAYER_SPEED: f32 = 700f32;
+// Default for the fastest the paddle may chase a target position (mouse, touch or bots)
+pub const POINTER_MAX_SPEED: f32 = 1200f32;
+pub const LASER_DURATION: f32 = 10f32;
+pub const LASER_COOLDOWN: f32 = 0.3f32;
+pub const CATCH_DURATION: f32 = 15f32;
+pub const MAGNET_DURATION: f32 = 15f32;
+pub const PLAYER_COLORS: [Color; 2] = [WHITE, SKYBLUE];
+// Top edge of a paddle guarding the top of the screen
+pub const TOP_PADDLE_Y: f32 = 80f32;
+
+// How the paddle accelerates and how much of its motion carries over into the ball
+#[derive(Clone, Copy)]
+pub struct PaddlePhysics {
+    // With inertia the paddle speeds up and slides to a stop instead of moving at full speed instantly
+    pub inertia: bool,
+    pub acceleration: f32,
+    pub friction: f32,
+    // Fraction of the paddle's velocity (relative to PLAYER_SPEED) added to the ball's x direction on contact
+    pub spin_factor: f32,
+}
+
+impl PaddlePhysics {
+    pub fn new() -> Self {
+        Self {
+            inertia: false,
+            acceleration: 4000f32,
+            friction: 3000f32,
+            spin_factor: 0.4f32,
+        }
+    }
+}
 
 pub struct Player {
     pub rect: Rect,
+    pub color: Color,
+    // Vertical direction the paddle plays towards: -1 (up) at the bottom of the screen, 1 (down) at the top
+    pub facing: f32,
+    pub vel: f32,
+    pub physics: PaddlePhysics,
+    pub material: SurfaceMaterial,
+    pub laser_timer: f32,
+    pub laser_cooldown: f32,
+    pub catch_timer: f32,
+    pub magnet_timer: f32,
 }
 
 impl Player {
-    pub fn new() -> Self {
+    // Paddles are spread evenly across the bottom of the screen
+    pub fn new(slot: usize, player_count: usize) -> Self {
+        let center_x = screen_width() * (slot + 1) as f32 / (player_count + 1) as f32;
         Self {
             rect: Rect::new(
-                screen_width() * 0.5f32 - PLAYER_SIZE.x*0.5f32,
+                center_x - PLAYER_SIZE.x*0.5f32,
                 screen_height() - 100f32,
                 PLAYER_SIZE.x,
                 PLAYER_SIZE.y,
             ),
+            color: PLAYER_COLORS[slot % PLAYER_COLORS.len()],
+            facing: -1f32,
+            vel: 0f32,
+            physics: PaddlePhysics::new(),
+            material: SurfaceMaterial::PADDLE,
+            laser_timer: 0f32,
+            laser_cooldown: 0f32,
+            catch_timer: 0f32,
+            magnet_timer: 0f32,
         }
     }
 
-    pub fn update(&mut self, dt: f32) {
-        let mut x_move = 0f32;
-        if is_key_down(KeyCode::Left) {
-            x_move -= 1f32;
-        }
-        if is_key_down(KeyCode::Right) {
-            x_move += 1f32;
+    // Paddle guarding the top of the screen and playing downwards
+    pub fn new_top(slot: usize) -> Self {
+        let mut player = Self::new(slot, 1);
+        player.rect.y = TOP_PADDLE_Y;
+        player.facing = 1f32;
+        player
+    }
+
+    // Changes the paddle's width around its centre
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.rect.x += (self.rect.w - width) * 0.5f32;
+        self.rect.w = width;
+        self
+    }
+
+    // `pointer_max_speed` caps how fast the paddle chases a target position
+    pub fn update(&mut self, dt: f32, intent: &PaddleIntent, pointer_max_speed: f32) {
+        let x_move = intent.x_move.clamp(-1f32, 1f32);
+        match intent.target_x {
+            Some(target_x) => self.move_towards(target_x, dt, pointer_max_speed),
+            None if self.physics.inertia => {
+                if x_move != 0f32 {
+                    self.vel += x_move * self.physics.acceleration * dt;
+                } else {
+                    // Slide to a stop without overshooting into the other direction
+                    let slowdown = self.physics.friction * dt;
+                    self.vel -= self.vel.clamp(-slowdown, slowdown);
+                }
+                self.vel = self.vel.clamp(-PLAYER_SPEED, PLAYER_SPEED);
+                self.rect.x += self.vel * dt;
+            }
+            None => {
+                self.vel = x_move * PLAYER_SPEED;
+                self.rect.x += self.vel * dt;
+            }
         }
-        self.rect.x += x_move * dt * PLAYER_SPEED;
 
         if self.rect.x < 0f32 {
             self.rect.x = 0f32;
+            self.vel = self.vel.max(0f32);
         }
         if self.rect.x > screen_width() - self.rect.w {
             self.rect.x = screen_width() - self.rect.w;
+            self.vel = self.vel.min(0f32);
         }
+
+        self.laser_timer = (self.laser_timer - dt).max(0f32);
+        self.laser_cooldown = (self.laser_cooldown - dt).max(0f32);
+        self.catch_timer = (self.catch_timer - dt).max(0f32);
+        self.magnet_timer = (self.magnet_timer - dt).max(0f32);
+    }
+
+    // Moves the paddle centre towards the pointer without exceeding the max speed
+    fn move_towards(&mut self, target_x: f32, dt: f32, max_speed: f32) {
+        let max_step = max_speed * dt;
+        let center_x = self.rect.x + self.rect.w * 0.5f32;
+        let step = (target_x - center_x).clamp(-max_step, max_step);
+        self.rect.x += step;
+        self.vel = if dt > 0f32 { step / dt } else { 0f32 };
+    }
+
+    // Spin imparted on a ball hitting the paddle right now
+    pub fn spin(&self) -> f32 {
+        self.vel / PLAYER_SPEED * self.physics.spin_factor
+    }
+
+    pub fn collider(&self) -> Collider {
+        Collider::new(self.rect, ColliderKind::Paddle, self.material)
+    }
+
+    pub fn has_laser(&self) -> bool {
+        self.laser_timer > 0f32
+    }
+
+    pub fn has_catch(&self) -> bool {
+        self.catch_timer > 0f32
+    }
+
+    pub fn has_magnet(&self) -> bool {
+        self.magnet_timer > 0f32
+    }
+
+    // Returns the muzzle positions of both cannons if the laser is ready to fire
+    pub fn fire_laser(&mut self) -> Option<[Vec2; 2]> {
+        if !self.has_laser() || self.laser_cooldown > 0f32 {
+            return None;
+        }
+        self.laser_cooldown = LASER_COOLDOWN;
+        Some(self.cannon_positions())
+    }
+
+    // Cannons sit on the playing side of the paddle
+    fn cannon_positions(&self) -> [Vec2; 2] {
+        let y = if self.facing < 0f32 { self.rect.y } else { self.rect.y + self.rect.h };
+        [
+            vec2(self.rect.x + self.rect.w * 0.15f32, y),
+            vec2(self.rect.x + self.rect.w * 0.85f32, y),
+        ]
     }
 
     pub fn draw(&self, texture_manager: &TextureManager) {
+        // Tint the paddle while it is sticky
+        let color = if self.has_catch() { LIME } else { self.color };
+
         if let Some(texture) = &texture_manager.paddle_texture {
             draw_texture_ex(
                 &texture,
                 self.rect.x,
                 self.rect.y,
-                WHITE,
+                color,
                 DrawTextureParams {
                     dest_size: Some(vec2(self.rect.w, self.rect.h)),
                     ..Default::default()
                 },
             );
         }
+
+        // Outline the paddle while the magnet is pulling in powerups
+        if self.has_magnet() {
+            draw_rectangle_lines(self.rect.x, self.rect.y, self.rect.w, self.rect.h, 3f32, PINK);
+        }
+
+        // Draw the cannons while the laser powerup is active
+        if self.has_laser() {
+            for cannon in self.cannon_positions() {
+                let y = if self.facing < 0f32 { cannon.y - 8f32 } else { cannon.y - 4f32 };
+                draw_rectangle(cannon.x - 4f32, y, 8f32, 12f32, RED);
+            }
+        }
     }
 }
\ No newline at end of file
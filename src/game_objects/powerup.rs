@@ -1,16 +1,43 @@
 use macroquad::prelude::*;
 use crate::game_objects::texture_manager::TextureManager;
 
+pub const MAGNET_PULL_SPEED: f32 = 250f32;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum PowerupType {
+    Grow,
+    Laser,
+    Catch,
+    Fireball,
+    Barrier,
+    Magnet,
+}
+
+impl PowerupType {
+    pub fn random() -> Self {
+        match rand::gen_range(0, 6) {
+            0 => PowerupType::Grow,
+            1 => PowerupType::Laser,
+            2 => PowerupType::Catch,
+            3 => PowerupType::Fireball,
+            4 => PowerupType::Barrier,
+            _ => PowerupType::Magnet,
+        }
+    }
+}
+
 pub struct Powerup {
     pub rect: Rect,
     pub vel: Vec2,
+    pub powerup_type: PowerupType,
 }
 
 impl Powerup {
-    pub fn new(pos: Vec2) -> Self {
+    pub fn new(pos: Vec2, powerup_type: PowerupType) -> Self {
         Self {
             rect: Rect::new(pos.x, pos.y, 30f32, 30f32),
             vel: vec2(0f32, 1f32),
+            powerup_type,
         }
     }
 
@@ -18,18 +45,62 @@ impl Powerup {
         self.rect.y += self.vel.y * dt * 200f32;
     }
 
+    // Pulls the powerup horizontally towards the given x position
+    pub fn attract(&mut self, target_x: f32, dt: f32) {
+        let center_x = self.rect.x + self.rect.w * 0.5f32;
+        let step = MAGNET_PULL_SPEED * dt;
+        self.rect.x += (target_x - center_x).clamp(-step, step);
+    }
+
+    pub fn is_offscreen(&self) -> bool {
+        self.rect.y > screen_height() || self.rect.y + self.rect.h < 0f32
+    }
+
+    // Letter drawn on the powerup in colourblind mode
+    fn symbol(&self) -> &'static str {
+        match self.powerup_type {
+            PowerupType::Grow => "G",
+            PowerupType::Laser => "L",
+            PowerupType::Catch => "C",
+            PowerupType::Fireball => "F",
+            PowerupType::Barrier => "B",
+            PowerupType::Magnet => "M",
+        }
+    }
+
     pub fn draw(&self, texture_manager: &TextureManager) {
+        let color = match self.powerup_type {
+            PowerupType::Grow => PURPLE,
+            PowerupType::Laser => RED,
+            PowerupType::Catch => LIME,
+            PowerupType::Fireball => ORANGE,
+            PowerupType::Barrier => SKYBLUE,
+            PowerupType::Magnet => PINK,
+        };
+
         if let Some(texture) = &texture_manager.power_up_texture {
             draw_texture_ex(
                 &texture,
                 self.rect.x,
                 self.rect.y,
-                PURPLE,
+                color,
                 DrawTextureParams {
                     dest_size: Some(vec2(self.rect.w, self.rect.h)),
                     ..Default::default()
                 },
             );
         }
+
+        if texture_manager.colorblind_mode {
+            let symbol = self.symbol();
+            let dims = measure_text(symbol, None, 24u16, 1f32);
+            draw_text(
+                symbol,
+                self.rect.center().x - dims.width * 0.5f32,
+                self.rect.center().y + dims.offset_y * 0.5f32,
+                24f32,
+                BLACK,
+            );
+        }
     }
 }
\ No newline at end of file
@@ -6,6 +6,8 @@ pub struct TextureManager {
     pub paddle_texture: Option<Texture2D>,
     pub power_up_texture: Option<Texture2D>,
     pub background_texture: Option<Texture2D>,
+    // Draw with a palette that doesn't rely on telling red from green, plus symbols on top
+    pub colorblind_mode: bool,
 }
 
 impl TextureManager {
@@ -16,6 +18,7 @@ impl TextureManager {
             paddle_texture: None,
             power_up_texture: None,
             background_texture: None,
+            colorblind_mode: false,
         }
     }
 
@@ -0,0 +1,346 @@
+use macroquad::prelude::*;
+use crate::difficulty::Difficulty;
+use crate::game_mode::GameMode;
+use crate::input::{Action, KeyBindings};
+use crate::storage;
+
+const HIGH_SCORES_KEY: &str = "high_scores";
+pub const MAX_HIGH_SCORES: usize = 10;
+const NAME_LENGTH: usize = 3;
+
+#[derive(Clone)]
+pub struct HighScore {
+    pub name: String,
+    pub score: i32,
+    // Level reached, or rows survived for survival runs
+    pub level: usize,
+    pub date: String,
+    pub mode: String,
+    pub difficulty: Difficulty,
+}
+
+impl HighScore {
+    // A score still waiting for its name, dated today
+    pub fn new(score: i32, level: usize, mode: &str, difficulty: Difficulty) -> Self {
+        Self {
+            name: String::new(),
+            score,
+            level,
+            date: format_date(miniquad::date::now()),
+            mode: mode.to_string(),
+            difficulty,
+        }
+    }
+
+    // `name,score,level,date,mode,difficulty`
+    fn parse(value: &str) -> Option<HighScore> {
+        let fields: Vec<&str> = value.split(',').map(|field| field.trim()).collect();
+        if fields.len() != 6 {
+            return None;
+        }
+        Some(HighScore {
+            name: fields[0].to_string(),
+            score: fields[1].parse().ok()?,
+            level: fields[2].parse().ok()?,
+            date: fields[3].to_string(),
+            mode: fields[4].to_string(),
+            difficulty: Difficulty::parse(fields[5])?,
+        })
+    }
+}
+
+// Best scores first, never more than MAX_HIGH_SCORES of them per difficulty,
+// so scores from easier presets don't crowd out the harder ones
+pub struct HighScoreTable {
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScoreTable {
+    pub fn load() -> Self {
+        let mut entries: Vec<HighScore> = storage::load(HIGH_SCORES_KEY)
+            .map(|text| {
+                storage::parse_entries(&text)
+                    .into_iter()
+                    .filter(|(name, _)| *name == "entry")
+                    .filter_map(|(_, value)| HighScore::parse(value))
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        let mut table = Self { entries };
+        for difficulty in Difficulty::ALL {
+            table.truncate(difficulty);
+        }
+        table
+    }
+
+    // The entries of one difficulty, best first
+    pub fn ranking(&self, difficulty: Difficulty) -> Vec<&HighScore> {
+        self.entries.iter().filter(|entry| entry.difficulty == difficulty).collect()
+    }
+
+    // Drops the entries of a difficulty that fell out of its top MAX_HIGH_SCORES
+    fn truncate(&mut self, difficulty: Difficulty) {
+        let mut kept = 0;
+        self.entries.retain(|entry| {
+            if entry.difficulty != difficulty {
+                return true;
+            }
+            kept += 1;
+            kept <= MAX_HIGH_SCORES
+        });
+    }
+
+    pub fn save(&self) {
+        let text: String = self
+            .entries
+            .iter()
+            .map(|entry| format!(
+                    "entry={},{},{},{},{},{}\n",
+                    entry.name,
+                    entry.score,
+                    entry.level,
+                    entry.date,
+                    entry.mode,
+                    entry.difficulty.label()
+                ))
+            .collect();
+        storage::save(HIGH_SCORES_KEY, &text);
+    }
+
+    pub fn qualifies(&self, score: i32, difficulty: Difficulty) -> bool {
+        let ranking = self.ranking(difficulty);
+        score > 0 && (ranking.len() < MAX_HIGH_SCORES || ranking.iter().any(|entry| score > entry.score))
+    }
+
+    // Adds the entry in its place and returns its rank within its difficulty, if it made the table
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        let difficulty = entry.difficulty;
+        let ranking = self.ranking(difficulty);
+        let rank = ranking.iter().position(|other| entry.score > other.score).unwrap_or(ranking.len());
+        if rank >= MAX_HIGH_SCORES {
+            return None;
+        }
+        let index = self.entries.iter().position(|other| entry.score > other.score).unwrap_or(self.entries.len());
+        self.entries.insert(index, entry);
+        self.truncate(difficulty);
+        Some(rank)
+    }
+
+    pub fn draw(&self, font: &Font, difficulty: Difficulty, highlight: &[usize]) {
+        let title = format!("High scores - {}", difficulty.label());
+        let title = title.as_str();
+        let title_dims = measure_text(title, Some(font), 50u16, 1.0f32);
+        draw_text_ex(
+            title,
+            screen_width() * 0.5f32 - title_dims.width * 0.5f32,
+            screen_height() * 0.15f32,
+            TextParams { font: Some(font), font_size: 50u16, color: BLACK, ..Default::default() },
+        );
+
+        let hint = "Left / right: other difficulties";
+        let hint_dims = measure_text(hint, Some(font), 20u16, 1.0f32);
+        draw_text_ex(
+            hint,
+            screen_width() * 0.5f32 - hint_dims.width * 0.5f32,
+            screen_height() * 0.95f32,
+            TextParams { font: Some(font), font_size: 20u16, color: BLACK, ..Default::default() },
+        );
+
+        let ranking = self.ranking(difficulty);
+        if ranking.is_empty() {
+            let empty_text = "No high scores yet";
+            let empty_dims = measure_text(empty_text, Some(font), 30u16, 1.0f32);
+            draw_text_ex(
+                empty_text,
+                screen_width() * 0.5f32 - empty_dims.width * 0.5f32,
+                screen_height() * 0.5f32,
+                TextParams { font: Some(font), font_size: 30u16, color: BLACK, ..Default::default() },
+            );
+            return;
+        }
+
+        let row_height = (screen_height() * 0.65f32 / MAX_HIGH_SCORES as f32).min(40f32);
+        let font_size = (row_height * 0.7f32) as u16;
+        let columns = [0.12f32, 0.2f32, 0.32f32, 0.45f32, 0.6f32];
+        for (rank, entry) in ranking.iter().enumerate() {
+            let y = screen_height() * 0.25f32 + rank as f32 * row_height;
+            if highlight.contains(&rank) {
+                draw_rectangle(screen_width() * 0.1f32, y - row_height * 0.75f32, screen_width() * 0.8f32, row_height, Color::new(1.0, 1.0, 1.0, 0.5));
+            }
+            let cells = [
+                format!("{}.", rank + 1),
+                entry.name.clone(),
+                entry.score.to_string(),
+                if entry.mode == GameMode::Survival.label() {
+                    format!("Rows {}", entry.level)
+                } else {
+                    format!("Level {}", entry.level)
+                },
+                format!("{}  {}", entry.date, entry.mode),
+            ];
+            for (cell, column) in cells.iter().zip(columns) {
+                draw_text_ex(
+                    cell,
+                    screen_width() * column,
+                    y,
+                    TextParams { font: Some(font), font_size, color: BLACK, ..Default::default() },
+                );
+            }
+        }
+    }
+}
+
+// Arcade style initials entry: up and down change the letter, confirm moves on to the next one
+pub struct NameEntry {
+    letters: [u8; NAME_LENGTH],
+    cursor: usize,
+}
+
+impl NameEntry {
+    pub fn new() -> Self {
+        Self {
+            letters: [b'A'; NAME_LENGTH],
+            cursor: 0,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.letters).to_string()
+    }
+
+    // Returns true once the last letter has been confirmed
+    pub fn update(&mut self, bindings: &KeyBindings, confirm: bool) -> bool {
+        let letter = &mut self.letters[self.cursor];
+        if bindings.is_pressed(Action::MenuUp) {
+            *letter = if *letter == b'Z' { b'A' } else { *letter + 1 };
+        }
+        if bindings.is_pressed(Action::MenuDown) {
+            *letter = if *letter == b'A' { b'Z' } else { *letter - 1 };
+        }
+        if bindings.is_pressed(Action::MoveLeft) {
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+        if bindings.is_pressed(Action::MoveRight) {
+            self.cursor = (self.cursor + 1).min(NAME_LENGTH - 1);
+        }
+        if confirm {
+            if self.cursor == NAME_LENGTH - 1 {
+                return true;
+            }
+            self.cursor += 1;
+        }
+        false
+    }
+
+    pub fn draw(&self, title: &str, font: &Font) {
+        let title_dims = measure_text(title, Some(font), 40u16, 1.0f32);
+        draw_text_ex(
+            title,
+            screen_width() * 0.5f32 - title_dims.width * 0.5f32,
+            screen_height() * 0.35f32,
+            TextParams { font: Some(font), font_size: 40u16, color: BLACK, ..Default::default() },
+        );
+
+        let letter_width = 60f32;
+        let start_x = screen_width() * 0.5f32 - letter_width * NAME_LENGTH as f32 * 0.5f32;
+        let y = screen_height() * 0.5f32;
+        for (i, letter) in self.letters.iter().enumerate() {
+            let x = start_x + i as f32 * letter_width;
+            let text = (*letter as char).to_string();
+            let dims = measure_text(&text, Some(font), 60u16, 1.0f32);
+            draw_text_ex(
+                &text,
+                x + letter_width * 0.5f32 - dims.width * 0.5f32,
+                y,
+                TextParams { font: Some(font), font_size: 60u16, color: BLACK, ..Default::default() },
+            );
+            if i == self.cursor {
+                draw_rectangle(x + 8f32, y + 10f32, letter_width - 16f32, 4f32, BLACK);
+            }
+        }
+    }
+}
+
+// YYYY-MM-DD for a unix timestamp, from Howard Hinnant's civil_from_days
+fn format_date(unix_seconds: f64) -> String {
+    let days = (unix_seconds / 86400.0).floor() as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: i32, difficulty: Difficulty) -> HighScore {
+        HighScore {
+            name: "AAA".to_string(),
+            score,
+            level: 1,
+            date: "2024-01-01".to_string(),
+            mode: "1 Player".to_string(),
+            difficulty,
+        }
+    }
+
+    fn scores(table: &HighScoreTable, difficulty: Difficulty) -> Vec<i32> {
+        table.ranking(difficulty).iter().map(|entry| entry.score).collect()
+    }
+
+    #[test]
+    fn insert_ranks_within_the_entry_difficulty() {
+        let mut table = HighScoreTable { entries: Vec::new() };
+        assert_eq!(table.insert(entry(100, Difficulty::Normal)), Some(0));
+        assert_eq!(table.insert(entry(300, Difficulty::Hard)), Some(0));
+        assert_eq!(table.insert(entry(200, Difficulty::Normal)), Some(0));
+        assert_eq!(table.insert(entry(50, Difficulty::Normal)), Some(2));
+        assert_eq!(table.insert(entry(250, Difficulty::Hard)), Some(1));
+        assert_eq!(scores(&table, Difficulty::Normal), vec![200, 100, 50]);
+        assert_eq!(scores(&table, Difficulty::Hard), vec![300, 250]);
+    }
+
+    #[test]
+    fn ties_go_below_the_earlier_score() {
+        let mut table = HighScoreTable { entries: Vec::new() };
+        table.insert(entry(100, Difficulty::Normal));
+        assert_eq!(table.insert(entry(100, Difficulty::Normal)), Some(1));
+    }
+
+    #[test]
+    fn a_full_difficulty_only_truncates_itself() {
+        let mut table = HighScoreTable { entries: Vec::new() };
+        // Interleave the two difficulties so their entries are mixed through the table
+        for i in 0..MAX_HIGH_SCORES as i32 {
+            table.insert(entry(1000 - i * 10, Difficulty::Normal));
+            table.insert(entry(995 - i * 10, Difficulty::Easy));
+        }
+        assert_eq!(table.insert(entry(1, Difficulty::Normal)), None);
+        assert_eq!(table.insert(entry(2000, Difficulty::Normal)), Some(0));
+        let normal = scores(&table, Difficulty::Normal);
+        assert_eq!(normal.len(), MAX_HIGH_SCORES);
+        assert_eq!(normal.first(), Some(&2000));
+        assert_eq!(normal.last(), Some(&920));
+        assert_eq!(scores(&table, Difficulty::Easy).len(), MAX_HIGH_SCORES);
+        assert_eq!(scores(&table, Difficulty::Easy).last(), Some(&905));
+    }
+
+    #[test]
+    fn qualifies_against_the_difficulty_table() {
+        let mut table = HighScoreTable { entries: Vec::new() };
+        assert!(!table.qualifies(0, Difficulty::Normal));
+        for i in 0..MAX_HIGH_SCORES as i32 {
+            table.insert(entry(100 + i, Difficulty::Normal));
+        }
+        assert!(!table.qualifies(100, Difficulty::Normal));
+        assert!(table.qualifies(101, Difficulty::Normal));
+        assert!(table.qualifies(1, Difficulty::Hard));
+    }
+}
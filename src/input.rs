@@ -0,0 +1,270 @@
+use macroquad::prelude::*;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    P2MoveLeft,
+    P2MoveRight,
+    P2Launch,
+    MenuUp,
+    MenuDown,
+    Launch,
+    AimLeft,
+    AimRight,
+    Pause,
+    Confirm,
+    Back,
+    // Development shortcuts, only read while dev mode is on
+    DevLevel(usize),
+    DevNextLevel,
+    DevPrevLevel,
+    DevAutopilot,
+    DevRecord,
+    DevToggle,
+}
+
+// Screens an action is read on. Actions that share one can't share a key.
+#[derive(PartialEq, Clone, Copy)]
+enum ActionGroup {
+    Play,
+    Menu,
+    Dev,
+}
+
+const DEV_LEVEL_LABELS: [&str; 10] = [
+    "Dev: level 1", "Dev: level 2", "Dev: level 3", "Dev: level 4", "Dev: level 5",
+    "Dev: level 6", "Dev: level 7", "Dev: level 8", "Dev: level 9", "Dev: level 10",
+];
+
+impl Action {
+    pub const ALL: [Action; 13] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::P2MoveLeft,
+        Action::P2MoveRight,
+        Action::P2Launch,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::Launch,
+        Action::AimLeft,
+        Action::AimRight,
+        Action::Pause,
+        Action::Confirm,
+        Action::Back,
+    ];
+
+    // Kept off the key bindings screen, but they can still be rebound in the settings file
+    pub const DEV: [Action; 15] = [
+        Action::DevLevel(1),
+        Action::DevLevel(2),
+        Action::DevLevel(3),
+        Action::DevLevel(4),
+        Action::DevLevel(5),
+        Action::DevLevel(6),
+        Action::DevLevel(7),
+        Action::DevLevel(8),
+        Action::DevLevel(9),
+        Action::DevLevel(10),
+        Action::DevNextLevel,
+        Action::DevPrevLevel,
+        Action::DevAutopilot,
+        Action::DevRecord,
+        Action::DevToggle,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::P2MoveLeft => "P2 move left",
+            Action::P2MoveRight => "P2 move right",
+            Action::P2Launch => "P2 launch / fire",
+            Action::MenuUp => "Menu up",
+            Action::MenuDown => "Menu down",
+            Action::Launch => "Launch / fire",
+            Action::AimLeft => "Aim left",
+            Action::AimRight => "Aim right",
+            Action::Pause => "Pause",
+            Action::Confirm => "Confirm",
+            Action::Back => "Back",
+            Action::DevLevel(level) => DEV_LEVEL_LABELS[(*level).clamp(1, 10) - 1],
+            Action::DevNextLevel => "Dev: next level",
+            Action::DevPrevLevel => "Dev: previous level",
+            Action::DevAutopilot => "Dev: autopilot",
+            Action::DevRecord => "Dev: record / replay",
+            Action::DevToggle => "Dev: toggle dev mode",
+        }
+    }
+
+    fn groups(&self) -> &'static [ActionGroup] {
+        match self {
+            // Left and right also step through options and initials
+            Action::MoveLeft | Action::MoveRight => &[ActionGroup::Play, ActionGroup::Menu],
+            Action::P2MoveLeft
+            | Action::P2MoveRight
+            | Action::P2Launch
+            | Action::Launch
+            | Action::AimLeft
+            | Action::AimRight
+            | Action::Pause => &[ActionGroup::Play],
+            Action::MenuUp | Action::MenuDown | Action::Confirm | Action::Back => &[ActionGroup::Menu],
+            // Dev shortcuts work on every screen
+            _ => &[ActionGroup::Play, ActionGroup::Menu, ActionGroup::Dev],
+        }
+    }
+
+    // Whether the two actions are read on the same screen, so one key can't trigger both
+    fn conflicts_with(&self, other: Action) -> bool {
+        *self != other && self.groups().iter().any(|group| other.groups().contains(group))
+    }
+
+    fn default_keys(&self) -> Vec<KeyCode> {
+        match self {
+            Action::MoveLeft => vec![KeyCode::Left],
+            Action::MoveRight => vec![KeyCode::Right],
+            Action::P2MoveLeft => vec![KeyCode::A],
+            Action::P2MoveRight => vec![KeyCode::D],
+            Action::P2Launch => vec![KeyCode::W],
+            Action::MenuUp => vec![KeyCode::Up, KeyCode::W],
+            Action::MenuDown => vec![KeyCode::Down, KeyCode::S],
+            Action::Launch => vec![KeyCode::Space],
+            Action::AimLeft => vec![KeyCode::Z],
+            Action::AimRight => vec![KeyCode::X],
+            Action::Pause => vec![KeyCode::Escape, KeyCode::P],
+            Action::Confirm => vec![KeyCode::Space, KeyCode::Enter],
+            Action::Back => vec![KeyCode::Escape, KeyCode::Backspace],
+            Action::DevLevel(10) => vec![KeyCode::Key0],
+            Action::DevLevel(level) => vec![[
+                KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5,
+                KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9,
+            ][(*level).clamp(1, 9) - 1]],
+            Action::DevNextLevel => vec![KeyCode::PageUp],
+            Action::DevPrevLevel => vec![KeyCode::PageDown],
+            Action::DevAutopilot => vec![KeyCode::F11],
+            Action::DevRecord => vec![KeyCode::F10],
+            Action::DevToggle => vec![KeyCode::F12],
+        }
+    }
+
+    // Name used for the action in the settings file
+    fn setting_name(&self) -> String {
+        format!("key.{:?}", self)
+    }
+}
+
+// Keys that can be bound to actions and stored in the settings file
+const BINDABLE_KEYS: &[KeyCode] = &[
+    KeyCode::A, KeyCode::B, KeyCode::C, KeyCode::D, KeyCode::E, KeyCode::F, KeyCode::G,
+    KeyCode::H, KeyCode::I, KeyCode::J, KeyCode::K, KeyCode::L, KeyCode::M, KeyCode::N,
+    KeyCode::O, KeyCode::P, KeyCode::Q, KeyCode::R, KeyCode::S, KeyCode::T, KeyCode::U,
+    KeyCode::V, KeyCode::W, KeyCode::X, KeyCode::Y, KeyCode::Z,
+    KeyCode::Key0, KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4,
+    KeyCode::Key5, KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9,
+    KeyCode::Left, KeyCode::Right, KeyCode::Up, KeyCode::Down,
+    KeyCode::Space, KeyCode::Enter, KeyCode::Escape, KeyCode::Tab, KeyCode::Backspace,
+    KeyCode::LeftShift, KeyCode::RightShift, KeyCode::LeftControl, KeyCode::RightControl,
+    KeyCode::LeftAlt, KeyCode::RightAlt,
+    KeyCode::Comma, KeyCode::Period, KeyCode::Slash, KeyCode::Semicolon, KeyCode::Apostrophe,
+    KeyCode::Minus, KeyCode::Equal, KeyCode::LeftBracket, KeyCode::RightBracket, KeyCode::Backslash,
+    KeyCode::Kp0, KeyCode::Kp1, KeyCode::Kp2, KeyCode::Kp3, KeyCode::Kp4,
+    KeyCode::Kp5, KeyCode::Kp6, KeyCode::Kp7, KeyCode::Kp8, KeyCode::Kp9, KeyCode::KpEnter,
+    KeyCode::PageUp, KeyCode::PageDown, KeyCode::Home, KeyCode::End, KeyCode::Insert, KeyCode::Delete,
+    KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6,
+    KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+];
+
+pub fn key_name(key: KeyCode) -> String {
+    format!("{:?}", key)
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS.iter().copied().find(|key| key_name(*key) == name)
+}
+
+pub fn is_bindable(key: KeyCode) -> bool {
+    BINDABLE_KEYS.contains(&key)
+}
+
+// Maps every action to the keys that trigger it
+#[derive(Clone)]
+pub struct KeyBindings {
+    keys: Vec<(Action, Vec<KeyCode>)>,
+}
+
+impl KeyBindings {
+    pub fn new() -> Self {
+        Self {
+            keys: Action::ALL.iter().chain(Action::DEV.iter()).map(|action| (*action, action.default_keys())).collect(),
+        }
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.keys
+            .iter()
+            .find(|(bound_action, _)| *bound_action == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    // Action on the same screen already bound to the key, which would make it ambiguous
+    pub fn conflict(&self, action: Action, key: KeyCode) -> Option<Action> {
+        self.keys
+            .iter()
+            .find(|(other, keys)| action.conflicts_with(*other) && keys.contains(&key))
+            .map(|(other, _)| *other)
+    }
+
+    // Makes `key` the action's only binding, unless an action it conflicts with already has it.
+    // Returns that action when the key is refused.
+    pub fn rebind(&mut self, action: Action, key: KeyCode) -> Result<(), Action> {
+        if let Some(other) = self.conflict(action, key) {
+            return Err(other);
+        }
+        if let Some((_, keys)) = self.keys.iter_mut().find(|(bound_action, _)| *bound_action == action) {
+            *keys = vec![key];
+        }
+        Ok(())
+    }
+
+    pub fn is_down(&self, action: Action) -> bool {
+        self.keys(action).iter().any(|key| is_key_down(*key))
+    }
+
+    pub fn is_pressed(&self, action: Action) -> bool {
+        self.keys(action).iter().any(|key| is_key_pressed(*key))
+    }
+
+    pub fn describe(&self, action: Action) -> String {
+        self.keys(action).iter().map(|key| key_name(*key)).collect::<Vec<_>>().join(" / ")
+    }
+
+    // Applies `key.<Action>=<Key>,<Key>` entries, ignoring anything unknown
+    pub fn apply_setting(&mut self, name: &str, value: &str) {
+        let Some(action) = Action::ALL.iter().chain(Action::DEV.iter()).find(|action| action.setting_name() == name) else {
+            return;
+        };
+        let keys: Vec<KeyCode> = value.split(',').filter_map(|key| parse_key(key.trim())).collect();
+        if let Some((_, bound_keys)) = self.keys.iter_mut().find(|(bound_action, _)| bound_action == action)
+            && !keys.is_empty()
+        {
+            *bound_keys = keys;
+        }
+    }
+
+    pub fn to_settings(&self) -> String {
+        self.keys
+            .iter()
+            .map(|(action, keys)| {
+                let keys: Vec<String> = keys.iter().map(|key| key_name(*key)).collect();
+                format!("{}={}\n", action.setting_name(), keys.join(","))
+            })
+            .collect()
+    }
+}
+
+// The action button for touch and mouse players: a click or a new touch
+pub fn is_pointer_pressed() -> bool {
+    is_mouse_button_pressed(MouseButton::Left)
+        || touches().iter().any(|touch| touch.phase == TouchPhase::Started)
+}
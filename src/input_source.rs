@@ -0,0 +1,275 @@
+use macroquad::prelude::*;
+use crate::game_objects::ball::Ball;
+use crate::input::{is_pointer_pressed, Action, KeyBindings};
+
+// What a paddle wants to do during one simulation step
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct PaddleIntent {
+    // Keyboard style movement between -1 and 1
+    pub x_move: f32,
+    // Position the paddle centre should chase instead, for pointer style control
+    pub target_x: Option<f32>,
+    // Launch held balls or fire the laser
+    pub launch: bool,
+}
+
+// Everything an input source may look at when deciding on an intent
+pub struct InputContext<'a> {
+    pub dt: f32,
+    pub paddle: Rect,
+    pub balls: &'a [Ball],
+    pub bindings: &'a KeyBindings,
+}
+
+// Supplies paddle intents, so a paddle can be steered by a person, a bot or a replay alike
+pub trait InputSource {
+    fn poll(&mut self, ctx: &InputContext) -> PaddleIntent;
+
+    // Sources with a natural end (replays) report when they have run out
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    // Length the next step must have, for sources that play back recorded steps
+    fn step_time(&self) -> Option<f32> {
+        None
+    }
+}
+
+// The actions that steer one paddle, so two players can share a keyboard
+#[derive(Clone, Copy)]
+pub struct ControlScheme {
+    pub left: Action,
+    pub right: Action,
+    pub launch: Action,
+}
+
+impl ControlScheme {
+    pub const PLAYER_ONE: ControlScheme = ControlScheme {
+        left: Action::MoveLeft,
+        right: Action::MoveRight,
+        launch: Action::Launch,
+    };
+
+    pub const PLAYER_TWO: ControlScheme = ControlScheme {
+        left: Action::P2MoveLeft,
+        right: Action::P2MoveRight,
+        launch: Action::P2Launch,
+    };
+}
+
+pub struct KeyboardInput {
+    pub scheme: ControlScheme,
+}
+
+impl InputSource for KeyboardInput {
+    fn poll(&mut self, ctx: &InputContext) -> PaddleIntent {
+        let mut x_move = 0f32;
+        if ctx.bindings.is_down(self.scheme.left) {
+            x_move -= 1f32;
+        }
+        if ctx.bindings.is_down(self.scheme.right) {
+            x_move += 1f32;
+        }
+        PaddleIntent {
+            x_move,
+            target_x: None,
+            launch: ctx.bindings.is_pressed(self.scheme.launch),
+        }
+    }
+}
+
+// Follows the mouse, or the first touch on touch screens
+pub struct PointerInput {
+    last_mouse_pos: Vec2,
+}
+
+impl PointerInput {
+    pub fn new() -> Self {
+        Self {
+            last_mouse_pos: Vec2::from(mouse_position()),
+        }
+    }
+
+    // True if the pointer moved or touched the screen since the last poll
+    fn was_used(&mut self) -> bool {
+        let mouse_pos = Vec2::from(mouse_position());
+        let moved = mouse_pos != self.last_mouse_pos;
+        self.last_mouse_pos = mouse_pos;
+        moved || !touches().is_empty()
+    }
+}
+
+impl InputSource for PointerInput {
+    fn poll(&mut self, _ctx: &InputContext) -> PaddleIntent {
+        let pointer = touches().first().map(|touch| touch.position).unwrap_or(Vec2::from(mouse_position()));
+        PaddleIntent {
+            x_move: 0f32,
+            target_x: Some(pointer.x),
+            launch: is_pointer_pressed(),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum ControlMethod {
+    Keyboard,
+    Pointer,
+}
+
+// How the first player wants to steer, picked in the options
+#[derive(PartialEq, Clone, Copy)]
+pub enum PaddleControl {
+    // Switch to whichever of keyboard and pointer was used last
+    Auto,
+    Keyboard,
+    Pointer,
+}
+
+impl PaddleControl {
+    pub const ALL: [PaddleControl; 3] = [PaddleControl::Auto, PaddleControl::Keyboard, PaddleControl::Pointer];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaddleControl::Auto => "Auto",
+            PaddleControl::Keyboard => "Keyboard",
+            PaddleControl::Pointer => "Mouse / touch",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<PaddleControl> {
+        PaddleControl::ALL.iter().copied().find(|control| control.label() == name)
+    }
+}
+
+// A local player on keyboard and pointer together, switching to whichever was used last
+pub struct LocalInput {
+    pub control: ControlMethod,
+    keyboard: KeyboardInput,
+    pointer: PointerInput,
+}
+
+impl LocalInput {
+    pub fn new(scheme: ControlScheme) -> Self {
+        Self {
+            control: ControlMethod::Keyboard,
+            keyboard: KeyboardInput { scheme },
+            pointer: PointerInput::new(),
+        }
+    }
+}
+
+impl InputSource for LocalInput {
+    fn poll(&mut self, ctx: &InputContext) -> PaddleIntent {
+        let keyboard = self.keyboard.poll(ctx);
+        let pointer = self.pointer.poll(ctx);
+
+        if keyboard.x_move != 0f32 {
+            self.control = ControlMethod::Keyboard;
+        } else if self.pointer.was_used() {
+            self.control = ControlMethod::Pointer;
+        }
+
+        let launch = keyboard.launch || pointer.launch;
+        match self.control {
+            ControlMethod::Keyboard => PaddleIntent { launch, ..keyboard },
+            ControlMethod::Pointer => PaddleIntent { launch, ..pointer },
+        }
+    }
+}
+
+// Intents recorded from the start of a level, each with the length of its step, along with the seed the
+// level was played with and where the run stood. Replaying it from the same start repeats the run exactly.
+pub struct Recording {
+    pub seed: u64,
+    pub level: usize,
+    pub score: i32,
+    pub lives: i32,
+    pub frames: Vec<(f32, PaddleIntent)>,
+}
+
+impl Recording {
+    pub fn new(seed: u64, level: usize, score: i32, lives: i32) -> Self {
+        Self { seed, level, score, lives, frames: Vec::new() }
+    }
+}
+
+// Plays back previously recorded intents, one per step
+pub struct ReplayInput {
+    frames: Vec<(f32, PaddleIntent)>,
+    index: usize,
+}
+
+impl ReplayInput {
+    pub fn new(frames: Vec<(f32, PaddleIntent)>) -> Self {
+        Self { frames, index: 0 }
+    }
+}
+
+impl InputSource for ReplayInput {
+    fn poll(&mut self, _ctx: &InputContext) -> PaddleIntent {
+        let intent = self.frames.get(self.index).map(|(_, intent)| *intent).unwrap_or_default();
+        self.index += 1;
+        intent
+    }
+
+    fn is_finished(&self) -> bool {
+        self.index >= self.frames.len()
+    }
+
+    fn step_time(&self) -> Option<f32> {
+        self.frames.get(self.index).map(|(dt, _)| *dt)
+    }
+}
+
+// Simple autopilot that keeps the paddle under the most threatening ball
+pub struct BotInput {
+    // Seconds to hold a caught ball before launching it
+    pub launch_delay: f32,
+    // Offset from the paddle centre the bot aims to hit with, so it doesn't always return straight up
+    aim_offset: f32,
+    hold_timer: f32,
+}
+
+impl BotInput {
+    pub fn new() -> Self {
+        Self {
+            launch_delay: 0.5f32,
+            aim_offset: 0f32,
+            hold_timer: 0f32,
+        }
+    }
+}
+
+impl InputSource for BotInput {
+    fn poll(&mut self, ctx: &InputContext) -> PaddleIntent {
+        let mut intent = PaddleIntent::default();
+
+        if ctx.balls.iter().any(|ball| ball.is_stuck()) {
+            self.hold_timer += ctx.dt;
+            if self.hold_timer >= self.launch_delay {
+                self.hold_timer = 0f32;
+                intent.launch = true;
+            }
+            return intent;
+        }
+        self.hold_timer = 0f32;
+
+        // Track the lowest ball that is falling, or the lowest ball at all if none are
+        let target = ctx
+            .balls
+            .iter()
+            .filter(|ball| ball.vel.y > 0f32)
+            .max_by(|a, b| a.rect.y.total_cmp(&b.rect.y))
+            .or_else(|| ctx.balls.iter().max_by(|a, b| a.rect.y.total_cmp(&b.rect.y)));
+
+        if let Some(ball) = target {
+            // Pick a new aim point each time the ball heads back up
+            if ball.vel.y < 0f32 {
+                self.aim_offset = rand::gen_range(-0.35f32, 0.35f32) * ctx.paddle.w;
+            }
+            intent.target_x = Some(ball.rect.center().x - self.aim_offset);
+        }
+        intent
+    }
+}
@@ -1,21 +1,171 @@
 use macroquad::prelude::*;
 
+mod achievements;
+mod difficulty;
+mod events;
+mod focus;
+mod game_mode;
 mod game_objects;
+mod high_scores;
+mod input;
+mod input_source;
+mod menu;
+mod puzzle;
+mod records;
+mod saved_run;
+mod scoring;
+mod session;
+mod settings;
+mod statistics;
+mod storage;
+mod survival;
+mod time_attack;
 use game_objects::{
-    ball::{Ball, BALL_SIZE},
+    ball::{Ball, FIREBALL_DURATION, MAX_BALL_SPEED, MIN_Y_VELOCITY},
+    barrier::Barrier,
     block::{Block, BlockType, BLOCK_SIZE},
-    player::{Player, PLAYER_SIZE},
-    powerup::Powerup,
+    collider::{wall_colliders, Collider, ColliderKind},
+    laser::Laser,
+    particle::Particle,
+    player::{Player, LASER_DURATION, CATCH_DURATION, MAGNET_DURATION},
+    powerup::{Powerup, PowerupType},
     texture_manager::TextureManager,
     audio_manager::AudioManager,
 };
+use input::{is_bindable, is_pointer_pressed, key_name, Action, KeyBindings};
+use game_mode::{GameMode, VERSUS_MISS_PENALTY};
+use difficulty::Difficulty;
+use achievements::Achievements;
+use events::GameEvent;
+use high_scores::{HighScore, HighScoreTable, NameEntry, MAX_HIGH_SCORES};
+use input_source::{BotInput, ControlScheme, InputContext, InputSource, KeyboardInput, LocalInput, PaddleControl, PointerInput, Recording, ReplayInput};
+use menu::Menu;
+use puzzle::{puzzle, Puzzle, PuzzleRun, PuzzleStars, PUZZLES, PUZZLE_STARS};
+use saved_run::SavedRun;
+use scoring::{block_points, ExtraLives, LevelScore, ScoreBreakdown, CHIP_POINTS, EXTRA_LIFE_FLASH};
+use session::{HotSeat, SessionSnapshot};
+use settings::Settings;
+use statistics::Statistics;
+use survival::{generate_row, Survival, SURVIVAL_COLUMNS, SURVIVAL_START_ROWS};
+use time_attack::{format_time, BestTimes, LevelTimer, ParTimes, BEST_TIMES};
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 pub enum GameState {
     Menu,
     Game,
+    Paused,
     LevelCompleted,
     Dead,
+    KeyBindings,
+    MatchOver,
+    NextTurn,
+    // Out of launches with blocks left, waiting to retry the puzzle
+    PuzzleFailed,
+    Options,
+    EnterName,
+    HighScores,
+    Achievements,
+    Statistics,
+}
+
+// Entries of the title screen menu
+#[derive(Clone, Copy)]
+enum TitleItem {
+    Continue,
+    Play(GameMode),
+    HighScores,
+    Achievements,
+    Statistics,
+    Options,
+}
+
+// Continue is only offered while there is a saved run
+fn title_items(has_saved_run: bool) -> Vec<TitleItem> {
+    let mut items = Vec::new();
+    if has_saved_run {
+        items.push(TitleItem::Continue);
+    }
+    items.extend([
+        TitleItem::Play(GameMode::Classic),
+        TitleItem::Play(GameMode::TimeAttack),
+        TitleItem::Play(GameMode::Survival),
+        TitleItem::Play(GameMode::Puzzle),
+        TitleItem::Play(GameMode::Coop),
+        TitleItem::Play(GameMode::Versus),
+        TitleItem::Play(GameMode::Alternating),
+        TitleItem::HighScores,
+        TitleItem::Achievements,
+        TitleItem::Statistics,
+        TitleItem::Options,
+    ]);
+    items
+}
+
+fn title_menu_items(items: &[TitleItem]) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| match item {
+            TitleItem::Continue => "Continue".to_string(),
+            TitleItem::Play(mode) => mode.label().to_string(),
+            TitleItem::HighScores => "High scores".to_string(),
+            TitleItem::Achievements => "Achievements".to_string(),
+            TitleItem::Statistics => "Statistics".to_string(),
+            TitleItem::Options => "Options".to_string(),
+        })
+        .collect()
+}
+
+// Input that steers the given player's paddle by default in a mode, honouring the first player's
+// preferred control. With two paddles only one of them can have the mouse, and the other gets the keyboard.
+fn default_input(mode: GameMode, slot: usize, control: PaddleControl) -> Box<dyn InputSource> {
+    let scheme = if slot == 0 { ControlScheme::PLAYER_ONE } else { ControlScheme::PLAYER_TWO };
+    match (mode.player_count(), control) {
+        (1, PaddleControl::Auto) => Box::new(LocalInput::new(scheme)),
+        (1, PaddleControl::Keyboard) => Box::new(KeyboardInput { scheme }),
+        (1, PaddleControl::Pointer) => Box::new(PointerInput::new()),
+        (_, PaddleControl::Pointer) if slot == 0 => Box::new(PointerInput::new()),
+        (_, PaddleControl::Pointer) => Box::new(KeyboardInput { scheme }),
+        _ if slot == 0 => Box::new(KeyboardInput { scheme }),
+        _ => Box::new(LocalInput::new(scheme)),
+    }
+}
+
+fn create_players(mode: GameMode, difficulty: Difficulty) -> Vec<Player> {
+    let players = if mode == GameMode::Versus {
+        vec![Player::new(0, 1), Player::new_top(1)]
+    } else {
+        let count = mode.player_count();
+        (0..count).map(|slot| Player::new(slot, count)).collect()
+    };
+    players
+        .into_iter()
+        .map(|player| {
+            let mut player = player.with_width(difficulty.paddle_width());
+            player.physics = difficulty.paddle_physics();
+            player.material.steering = difficulty.paddle_steering();
+            player
+        })
+        .collect()
+}
+
+// A new ball resting on the given player's paddle
+fn serve_ball(player: &Player, slot: usize, difficulty: Difficulty) -> Ball {
+    Ball::new_on_paddle(&player.rect, player.facing, slot).with_base_speed(difficulty.ball_speed())
+}
+
+// Player who let the ball out of the playfield, if it left it.
+// Versus paddles each guard their own side, otherwise the last player to touch the ball lost it.
+fn missed_by(ball: &Ball, players: &[Player], mode: GameMode) -> Option<usize> {
+    let through_bottom = ball.rect.y >= screen_height();
+    let through_top = !mode.has_ceiling() && ball.rect.y + ball.rect.h <= 0f32;
+    if !through_bottom && !through_top {
+        return None;
+    }
+    if mode == GameMode::Versus {
+        players.iter().position(|player| (player.facing < 0f32) == through_bottom)
+    } else {
+        Some(ball.owner)
+    }
 }
 
 pub fn draw_title_text(text: &str, font: &Font) {
@@ -28,8 +178,96 @@ pub fn draw_title_text(text: &str, font: &Font) {
     );
 }
 
-// collision with positional correction
-fn resolve_collision(a: &mut Rect, vel: &mut Vec2, b: &Rect) -> bool {
+// A bound key, a mouse click or a new touch all count as pressing the action
+fn is_action_pressed(bindings: &KeyBindings, action: Action) -> bool {
+    bindings.is_pressed(action) || is_pointer_pressed()
+}
+
+// Entries of the options screen
+#[derive(Clone, Copy, PartialEq)]
+enum OptionItem {
+    MusicVolume,
+    SfxVolume,
+    Difficulty,
+    PaddleControl,
+    Fullscreen,
+    ColorblindMode,
+    KeyBindings,
+    Back,
+}
+
+const OPTION_ITEMS: [OptionItem; 8] = [
+    OptionItem::MusicVolume,
+    OptionItem::SfxVolume,
+    OptionItem::Difficulty,
+    OptionItem::PaddleControl,
+    OptionItem::Fullscreen,
+    OptionItem::ColorblindMode,
+    OptionItem::KeyBindings,
+    OptionItem::Back,
+];
+
+fn on_off(value: bool) -> &'static str {
+    if value { "On" } else { "Off" }
+}
+
+fn options_menu_items(settings: &Settings) -> Vec<String> {
+    OPTION_ITEMS
+        .iter()
+        .map(|item| match item {
+            OptionItem::MusicVolume => format!("Music volume: {}%", (settings.music_volume * 100.0).round()),
+            OptionItem::SfxVolume => format!("SFX volume: {}%", (settings.sfx_volume * 100.0).round()),
+            OptionItem::Difficulty => format!("Difficulty: {}", settings.difficulty.label()),
+            OptionItem::PaddleControl => format!("Paddle control: {}", settings.paddle_control.label()),
+            OptionItem::Fullscreen => format!("Fullscreen: {}", on_off(settings.fullscreen)),
+            OptionItem::ColorblindMode => format!("Colourblind mode: {}", on_off(settings.colorblind_mode)),
+            OptionItem::KeyBindings => "Key bindings".to_string(),
+            OptionItem::Back => "Back".to_string(),
+        })
+        .collect()
+}
+
+// Moves `step` entries through `all` from `current`, wrapping around at both ends
+fn cycle<T: Copy + PartialEq>(all: &[T], current: T, step: i32) -> T {
+    let index = all.iter().position(|value| *value == current).unwrap_or(0) as i32;
+    all[(index + step).rem_euclid(all.len() as i32) as usize]
+}
+
+// Volumes go in steps of 10%, wrapping from 100% back to silence
+fn step_volume(volume: f32, step: i32) -> f32 {
+    ((volume * 10.0).round() as i32 + step).rem_euclid(11) as f32 / 10.0
+}
+
+fn change_option(settings: &mut Settings, item: OptionItem, step: i32) {
+    match item {
+        OptionItem::MusicVolume => settings.music_volume = step_volume(settings.music_volume, step),
+        OptionItem::SfxVolume => settings.sfx_volume = step_volume(settings.sfx_volume, step),
+        OptionItem::Difficulty => settings.difficulty = cycle(&Difficulty::ALL, settings.difficulty, step),
+        OptionItem::PaddleControl => settings.paddle_control = cycle(&PaddleControl::ALL, settings.paddle_control, step),
+        OptionItem::Fullscreen => settings.fullscreen = !settings.fullscreen,
+        OptionItem::ColorblindMode => settings.colorblind_mode = !settings.colorblind_mode,
+        OptionItem::KeyBindings | OptionItem::Back => {}
+    }
+}
+
+const PAUSE_MENU_ITEMS: [&str; 4] = ["Resume", "Restart level", "Options", "Quit to title"];
+
+fn key_bindings_menu_items(bindings: &KeyBindings) -> Vec<String> {
+    let mut items: Vec<String> = Action::ALL
+        .iter()
+        .map(|action| format!("{}: {}", action.label(), bindings.describe(*action)))
+        .collect();
+    items.push("Back".to_string());
+    items
+}
+
+// collision with positional correction, bouncing the ball the way the surface's material declares
+fn resolve_collision(ball: &mut Ball, collider: &Collider) -> bool {
+    let a = &mut ball.rect;
+    let vel = &mut ball.vel;
+    let b = &collider.rect;
+    let material = &collider.material;
+
     // Early exit if no collision
     let intersection = match a.intersect(*b) {
         Some(intersection) => intersection,
@@ -49,24 +287,25 @@ fn resolve_collision(a: &mut Rect, vel: &mut Vec2, b: &Rect) -> bool {
             vel.y = vel.y.abs();
         }
 
-        // Adjust trajectory if colliding with the paddle
-        if b.h == PLAYER_SIZE.y {
-            let paddle_center = b.x + b.w * 0.5;
+        // Steer the trajectory by where the ball hit the surface
+        if material.steering != 0f32 {
+            let surface_center = b.x + b.w * 0.5;
             let ball_center = a.x + a.w * 0.5;
-            let relative_hit_pos = (ball_center - paddle_center) / (b.w * 0.5);
+            let relative_hit_pos = (ball_center - surface_center) / (b.w * 0.5);
 
             // Adjust the x velocity based on the relative hit position
-            vel.x += relative_hit_pos * 1.5; // Increase multiplier for sharper angles
+            vel.x += material.steer(relative_hit_pos);
 
             // Normalize to maintain consistent speed
             *vel = vel.normalize();
 
             // Clamp the angle to prevent excessive sharpness
-            let min_y_velocity = 0.5; // Minimum y component to avoid shallow angles
-            if vel.y.abs() < min_y_velocity {
-                vel.y = vel.y.signum() * min_y_velocity;
+            if vel.y.abs() < MIN_Y_VELOCITY {
+                vel.y = vel.y.signum() * MIN_Y_VELOCITY;
                 *vel = vel.normalize(); // Re-normalize after clamping
             }
+        } else {
+            *vel = vel.normalize();
         }
     } else {
         // Bounce on x-axis
@@ -77,56 +316,206 @@ fn resolve_collision(a: &mut Rect, vel: &mut Vec2, b: &Rect) -> bool {
         } else {
             vel.x = -vel.x.abs();
         }
+        *vel = vel.normalize();
     }
 
+    ball.speed = (ball.speed * material.speed_multiplier).min(MAX_BALL_SPEED);
+
     true
 }
 
+fn collision_sound(kind: ColliderKind) -> &'static str {
+    match kind {
+        ColliderKind::Paddle => "bounce",
+        ColliderKind::Block => "block_hit",
+        ColliderKind::Wall | ColliderKind::SpeedPad => "wall_hit",
+    }
+}
+
+// What destroyed blocks leave behind, which depends on the mode and difficulty
+struct DropRules {
+    ball_speed: f32,
+    powerup_chance: f32,
+    // Puzzles send spawned balls off along the player's aim rather than at random
+    ball_angle: Option<f32>,
+}
+
+impl DropRules {
+    fn new(game_mode: GameMode, difficulty: Difficulty, aim: f32) -> Self {
+        let puzzle = game_mode == GameMode::Puzzle;
+        Self {
+            ball_speed: difficulty.ball_speed(),
+            powerup_chance: if puzzle { 0f32 } else { difficulty.powerup_drop_chance() },
+            ball_angle: puzzle.then_some(aim),
+        }
+    }
+}
+
+// Applies damage to a block and handles drops once it breaks, returning the points earned.
+// Shared by everything that can damage blocks (balls, fireballs and lasers).
+// Dropped powerups fall in `fall_dir`, towards the paddle of whoever broke the block.
+#[allow(clippy::too_many_arguments)]
+fn damage_block(
+    block: &mut Block,
+    damage: i32,
+    owner: usize,
+    fall_dir: f32,
+    spawn_pos: Vec2,
+    drops: &DropRules,
+    new_balls: &mut Vec<Ball>,
+    powerups: &mut Vec<Powerup>,
+    events: &mut Vec<GameEvent>,
+    audio_manager: &AudioManager,
+) -> i32 {
+    audio_manager.play_sound_effect(collision_sound(block.collider().kind));
+    // A block already destroyed earlier this frame, e.g. by another ball, is only removed at the end of it
+    if !block.is_destructible() || block.lives <= 0 {
+        return 0;
+    }
+
+    block.lives -= damage;
+    if block.lives > 0 {
+        return CHIP_POINTS;
+    }
+
+    audio_manager.play_sound_effect("block_destroyed");
+    events.push(GameEvent::BlockDestroyed(block.block_type));
+    if block.block_type == BlockType::SpawnBallOnDeath {
+        let ball = match drops.ball_angle {
+            Some(angle) => Ball::aimed(spawn_pos, angle),
+            None => Ball::new(spawn_pos),
+        };
+        let mut ball = ball.with_base_speed(drops.ball_speed);
+        ball.owner = owner;
+        new_balls.push(ball);
+    } else if block.block_type == BlockType::SpawnPowerup || rand::gen_range(0f32, 1f32) < drops.powerup_chance {
+        let mut powerup = Powerup::new(block.rect.point(), PowerupType::random());
+        powerup.vel.y = fall_dir;
+        powerups.push(powerup);
+    }
+    block_points(block.block_type)
+}
+
+// All points are scored through here, so an extra life is awarded the moment the score passes a threshold.
+// Points are also credited to `owner`'s share. Returns true if an extra life was awarded.
+#[allow(clippy::too_many_arguments)]
+fn add_points(
+    points: i32,
+    owner: usize,
+    score: &mut i32,
+    player_scores: &mut [i32],
+    player_lives: &mut i32,
+    extra_lives: &ExtraLives,
+    game_mode: GameMode,
+    audio_manager: &AudioManager,
+) -> bool {
+    let before = *score;
+    *score += points;
+    player_scores[owner] += points;
+    let earned = if game_mode.has_lives() { extra_lives.earned(before, *score) } else { 0 };
+    if earned > 0 {
+        *player_lives += earned;
+        audio_manager.play_sound_effect("extra_life");
+    }
+    earned > 0
+}
+
+// What the modes keep track of over a single level, started afresh whenever a level starts
+#[derive(Default)]
+struct LevelState {
+    timer: LevelTimer,
+    survival: Survival,
+    puzzle_run: PuzzleRun,
+    score: LevelScore,
+    // Targets of the level being played, looked up once when it starts
+    par_times: ParTimes,
+}
+
+impl LevelState {
+    fn reset(&mut self, par_times: ParTimes) {
+        *self = Self { par_times, ..Self::default() };
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn reset_game(
     score: &mut i32,
     player_lives: &mut i32,
     blocks: &mut Vec<Block>,
     balls: &mut Vec<Ball>,
-    player: &mut Player,
+    lasers: &mut Vec<Laser>,
+    barrier: &mut Option<Barrier>,
+    players: &mut Vec<Player>,
+    level_state: &mut LevelState,
+    game_mode: GameMode,
+    difficulty: Difficulty,
     current_level: usize,
     level_completed: bool,
 ) {
-    *player = Player::new();
-    init_blocks(blocks, current_level);
+    *players = create_players(game_mode, difficulty);
+    lasers.clear();
+    *barrier = None;
+    level_state.reset(init_blocks(blocks, current_level, game_mode));
 
     if !level_completed {
         // Reset everything for game over
         balls.clear();
         *score = 0;
-        *player_lives = 3;
-        balls.push(Ball::new(vec2(screen_width() * 0.5f32, screen_height() * 0.5f32)));
+        *player_lives = difficulty.starting_lives();
+        balls.push(serve_ball(&players[0], 0, difficulty));
     } else {
         // Just reset ball position for next level
         balls.clear();
-        balls.push(Ball::new(vec2(screen_width() * 0.5f32, screen_height() * 0.5f32)));
+        balls.push(serve_ball(&players[0], 0, difficulty));
     }
 }
 
-fn init_blocks(blocks: &mut Vec<Block>, level: usize) {
+// Gap between neighbouring blocks
+const BOARD_PADDING: f32 = 2.0;
+// Where the board starts below the HUD
+const BOARD_TOP: f32 = 50.0;
+
+// Block size and left edge of a board `columns` blocks wide, centred on the screen
+fn board_geometry(columns: usize) -> (Vec2, f32) {
+    let available_width = screen_width() * 0.9;
+    let block_width = (available_width - (BOARD_PADDING * (columns as f32 - 1.0))) / columns as f32;
+    let block_height = block_width * (BLOCK_SIZE.y / BLOCK_SIZE.x);
+    let board_width = (columns as f32 * block_width) + ((columns - 1) as f32 * BOARD_PADDING);
+    (vec2(block_width, block_height), (screen_width() - board_width) * 0.5)
+}
+
+// Lays out the level's board and returns its par times
+fn init_blocks(blocks: &mut Vec<Block>, level: usize, game_mode: GameMode) -> ParTimes {
     blocks.clear();
+
+    // Survival has no levels, just a few generated rows to start from, and nothing to time
+    if game_mode == GameMode::Survival {
+        let (block_size, start_x) = board_geometry(SURVIVAL_COLUMNS);
+        for row in 0..SURVIVAL_START_ROWS {
+            let y = BOARD_TOP + row as f32 * (block_size.y + BOARD_PADDING);
+            blocks.extend(generate_row(start_x, y, block_size, BOARD_PADDING, 0));
+        }
+        return ParTimes::default();
+    }
     
     // Base configuration for board dimensions
-    let padding = 2.0;
-    let available_width = screen_width() * 0.9;
+    let padding = BOARD_PADDING;
     
     // Get level layout based on current level
-    let layout = get_level_layout(level);
+    let layout = level_layout(level, game_mode);
     let (width, height) = (layout.width, layout.height);
     
     // Calculate block size based on width
-    let block_width = (available_width - (padding * (width as f32 - 1.0))) / width as f32;
-    let block_height = block_width * (BLOCK_SIZE.y / BLOCK_SIZE.x);
-    let actual_block_size = vec2(block_width, block_height);
+    let (actual_block_size, board_start_x) = board_geometry(width);
+    let (block_width, block_height) = (actual_block_size.x, actual_block_size.y);
     
-    // Calculate board dimensions
-    let board_width = (width as f32 * block_width) + ((width - 1) as f32 * padding);
-    let board_start_x = (screen_width() - board_width) * 0.5;
-    let board_start_y = 50f32;
+    // Without a ceiling the board sits in the middle band between the two paddles
+    let board_height = (height as f32 * block_height) + ((height - 1) as f32 * padding);
+    let board_start_y = if game_mode.has_ceiling() {
+        BOARD_TOP
+    } else {
+        (screen_height() - board_height) * 0.5
+    };
     
     // Create block layout based on the pattern
     let mut temp_blocks = Vec::new();
@@ -151,15 +540,18 @@ fn init_blocks(blocks: &mut Vec<Block>, level: usize) {
         }
     }
     
-    // Ensure at least one powerup block per level if we don't already have one
-    if !temp_blocks.iter().any(|b| b.block_type == BlockType::SpawnPowerup) && !temp_blocks.is_empty() {
-        let random_index = rand::gen_range(0, temp_blocks.len());
-        if let Some(block) = temp_blocks.get_mut(random_index) {
-            block.block_type = BlockType::SpawnPowerup;
+    // Ensure at least one powerup block per level if we don't already have one.
+    // Puzzles leave nothing to chance, so they only get what they were made with.
+    if game_mode != GameMode::Puzzle && !temp_blocks.iter().any(|b| b.block_type == BlockType::SpawnPowerup) {
+        let mut candidates: Vec<&mut Block> = temp_blocks.iter_mut().filter(|b| b.is_destructible()).collect();
+        if !candidates.is_empty() {
+            let random_index = rand::gen_range(0, candidates.len());
+            candidates[random_index].block_type = BlockType::SpawnPowerup;
         }
     }
     
     *blocks = temp_blocks;
+    layout.times
 }
 
 // Structure to define a level layout
@@ -167,6 +559,8 @@ struct LevelLayout {
     width: usize,
     height: usize,
     pattern: Vec<Option<BlockType>>,
+    // Time attack targets for clearing the level
+    times: ParTimes,
 }
 
 impl LevelLayout {
@@ -202,7 +596,7 @@ fn get_level_layout(level: usize) -> LevelLayout {
             // Add a power-up block
             pattern[1 * width + 1] = Some(BlockType::SpawnPowerup);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(60.0, 35.0) }
         },
         
         2 => {
@@ -227,7 +621,7 @@ fn get_level_layout(level: usize) -> LevelLayout {
             // Add a power-up block
             pattern[3 * width + 5] = Some(BlockType::SpawnPowerup);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(70.0, 40.0) }
         },
         
         3 => {
@@ -258,7 +652,7 @@ fn get_level_layout(level: usize) -> LevelLayout {
             pattern[2 * width + 4] = Some(BlockType::SpawnPowerup);
             pattern[3 * width + 4] = Some(BlockType::Strong);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(90.0, 55.0) }
         },
         
         4 => {
@@ -297,7 +691,7 @@ fn get_level_layout(level: usize) -> LevelLayout {
             pattern[1 * width + 5] = Some(BlockType::SpawnPowerup);
             pattern[3 * width + 5] = Some(BlockType::SpawnBallOnDeath);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(100.0, 60.0) }
         },
         
         5 => {
@@ -330,7 +724,7 @@ fn get_level_layout(level: usize) -> LevelLayout {
             pattern[2 * width + 2] = Some(BlockType::SpawnPowerup);
             pattern[4 * width + 8] = Some(BlockType::SpawnPowerup);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(110.0, 65.0) }
         },
         
         6 => {
@@ -363,11 +757,15 @@ fn get_level_layout(level: usize) -> LevelLayout {
                 }
             }
             
+            // Speed pads at both ends of the main path
+            pattern[3 * width] = Some(BlockType::SpeedPad);
+            pattern[3 * width + (width - 1)] = Some(BlockType::SpeedPad);
+            
             // Add power-ups
             pattern[1 * width + 3] = Some(BlockType::SpawnPowerup);
             pattern[5 * width + 8] = Some(BlockType::SpawnBallOnDeath);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(120.0, 75.0) }
         },
         
         7 => {
@@ -411,7 +809,7 @@ fn get_level_layout(level: usize) -> LevelLayout {
             pattern[1 * width + center_x] = Some(BlockType::SpawnPowerup);
             pattern[(height - 2) * width + center_x] = Some(BlockType::SpawnBallOnDeath);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(120.0, 75.0) }
         },
         
         8 => {
@@ -456,7 +854,7 @@ fn get_level_layout(level: usize) -> LevelLayout {
             pattern[1 * width + 7] = Some(BlockType::SpawnPowerup);
             pattern[6 * width + 1] = Some(BlockType::SpawnBallOnDeath);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(130.0, 80.0) }
         },
         
         9 => {
@@ -503,7 +901,7 @@ fn get_level_layout(level: usize) -> LevelLayout {
             pattern[2 * width + 5] = Some(BlockType::SpawnPowerup);
             pattern[6 * width + 5] = Some(BlockType::SpawnBallOnDeath);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(150.0, 90.0) }
         },
         
         10 => {
@@ -551,18 +949,12 @@ fn get_level_layout(level: usize) -> LevelLayout {
             pattern[6 * width + 9] = Some(BlockType::Strong);
             pattern[8 * width + 12] = Some(BlockType::Strong);
             
-            // Add a few more medium blocks to increase difficulty
-            pattern[0 * width + 0] = Some(BlockType::Medium);
-            pattern[0 * width + width-1] = Some(BlockType::Medium);
-            pattern[(height-1) * width + 0] = Some(BlockType::Medium);
-            pattern[(height-1) * width + width-1] = Some(BlockType::Medium);
-            
             // Add power-ups
             pattern[2 * width + 7] = Some(BlockType::SpawnPowerup);
             pattern[6 * width + 3] = Some(BlockType::SpawnPowerup);
             pattern[8 * width + 10] = Some(BlockType::SpawnBallOnDeath);
             
-            LevelLayout { width, height, pattern }
+            LevelLayout { width, height, pattern, times: ParTimes::new(160.0, 100.0) }
         },
         
         // Default case for levels beyond 10 (loops back to level 1-10 patterns but with increased difficulty)
@@ -591,19 +983,70 @@ fn get_level_layout(level: usize) -> LevelLayout {
                 width: layout.width,
                 height: layout.height,
                 pattern,
+                times: layout.times,
             }
         }
     }
 }
 
-fn handle_powerup_collision(player: &mut Player, powerups: &mut Vec<Powerup>, audio_manager: &AudioManager) {
+// Puzzles are played for launches rather than against the clock, so they have no par times
+fn puzzle_layout(puzzle: &Puzzle) -> LevelLayout {
+    let (width, height) = (puzzle.width(), puzzle.height());
+    let pattern = (0..width * height).map(|i| puzzle.block_at(i % width, i / width)).collect();
+    LevelLayout { width, height, pattern, times: ParTimes::new(0.0, 0.0) }
+}
+
+fn level_layout(level: usize, game_mode: GameMode) -> LevelLayout {
+    if game_mode == GameMode::Puzzle {
+        puzzle_layout(puzzle(level))
+    } else {
+        get_level_layout(level)
+    }
+}
+
+// Builds the whole layout, so it is only looked up when a level starts
+fn level_par_times(level: usize, game_mode: GameMode) -> ParTimes {
+    level_layout(level, game_mode).times
+}
+
+fn handle_powerup_collision(
+    players: &mut [Player],
+    balls: &mut [Ball],
+    barrier: &mut Option<Barrier>,
+    powerups: &mut Vec<Powerup>,
+    events: &mut Vec<GameEvent>,
+    audio_manager: &AudioManager,
+) {
     let max_paddle_width = screen_width() / 3.0;
     
     powerups.retain(|powerup| {
-        if powerup.rect.overlaps(&player.rect) {
-            // Apply powerup effect: Increase paddle size, but limit to max width
-            player.rect.w = (player.rect.w + 50f32).min(max_paddle_width);
+        // Whoever catches the powerup gets its effect
+        if let Some(player) = players.iter_mut().find(|player| powerup.rect.overlaps(&player.rect)) {
+            match powerup.powerup_type {
+                PowerupType::Grow => {
+                    // Increase paddle size, but limit to max width
+                    player.rect.w = (player.rect.w + 50f32).min(max_paddle_width);
+                }
+                PowerupType::Laser => {
+                    player.laser_timer = LASER_DURATION;
+                }
+                PowerupType::Catch => {
+                    player.catch_timer = CATCH_DURATION;
+                }
+                PowerupType::Fireball => {
+                    for ball in balls.iter_mut() {
+                        ball.fireball_timer = FIREBALL_DURATION;
+                    }
+                }
+                PowerupType::Barrier => {
+                    *barrier = Some(Barrier::new(player.facing));
+                }
+                PowerupType::Magnet => {
+                    player.magnet_timer = MAGNET_DURATION;
+                }
+            }
             audio_manager.play_sound_effect("powerup_collected");
+            events.push(GameEvent::PowerupCollected);
             false // Remove the powerup after collision
         } else {
             true
@@ -629,81 +1072,163 @@ async fn main() {
     // Initialize and load audio
     let mut audio_manager = AudioManager::new();
     audio_manager.load_sounds(base_path).await;
+
+    let mut settings = Settings::load();
+    settings.apply(&mut audio_manager, &mut texture_manager);
+    if settings.fullscreen {
+        set_fullscreen(true);
+    }
     audio_manager.play_background_music();
 
     let mut game_state = GameState::Menu;
+    let mut game_mode = GameMode::Classic;
+    let mut has_saved_run = SavedRun::exists();
+    let mut title_menu = Menu::new(&[]);
+    let mut key_bindings_menu = Menu::new(&[]);
+    let mut options_menu = Menu::new(&[]);
+    let mut pause_menu = Menu::new(&PAUSE_MENU_ITEMS);
+    // Where leaving the options screen goes back to, the title or the pause menu
+    let mut options_return_state = GameState::Menu;
+    let mut high_score_table = HighScoreTable::load();
+    // Difficulty whose table the high score screen shows
+    let mut high_score_difficulty = settings.difficulty;
+    // Finished runs that made the table, with the player they belong to, waiting for initials
+    let mut pending_high_scores: Vec<(usize, HighScore)> = Vec::new();
+    let mut name_entry = NameEntry::new();
+    // Ranks of the entries just added, highlighted on the high score screen
+    let mut new_high_score_ranks: Vec<usize> = Vec::new();
+    // Action waiting for a new key on the key bindings screen
+    let mut rebinding: Option<Action> = None;
+    // Key refused by the last rebind and the action already using it
+    let mut rebind_conflict: Option<(KeyCode, Action)> = None;
     let mut score = 0;
-    let mut player_lives = 3;
+    // Each player's share of the score
+    let mut player_scores = vec![0; game_mode.player_count()];
+    // Difficulty of the run in progress, the options only change it for the next run
+    let mut run_difficulty = settings.difficulty;
+    let mut player_lives = run_difficulty.starting_lives();
     let mut current_level = 1;
-    let mut player = Player::new();
+    let mut players = create_players(game_mode, run_difficulty);
+    // Parked sessions of the players waiting for their turn in alternating play
+    let mut hot_seat: Option<HotSeat> = None;
     let mut blocks = Vec::new();
     let mut balls = Vec::new();
     let mut powerups = Vec::new();
+    let mut lasers = Vec::new();
+    let mut barrier: Option<Barrier> = None;
+    let mut particles: Vec<Particle> = Vec::new();
     let mut level_completed: bool = false;
+    let mut level_state = LevelState::default();
+    // Counts down while the HUD celebrates an extra life
+    let mut extra_life_flash = 0f32;
+    let mut achievements = Achievements::load();
+    let mut statistics = Statistics::load();
+    // What happened in play this frame, for the achievements and statistics to look at
+    let mut events: Vec<GameEvent> = Vec::new();
+    // Where the points of the level just cleared came from
+    let mut level_breakdown: Option<ScoreBreakdown> = None;
+    let mut puzzle_stars = PuzzleStars::load(PUZZLE_STARS);
+    // Stars earned on the puzzle just cleared and whether they beat its best
+    let mut puzzle_result: Option<(u32, bool)> = None;
+    let mut best_times = BestTimes::load(BEST_TIMES);
+    // Time attack result of the level just cleared, its time and whether it was a new best
+    let mut level_result: Option<(f32, bool)> = None;
     
+    // Whoever is steering each paddle; local players unless a dev shortcut hands the first one to a bot or replay
+    let mut paddle_inputs: Vec<Box<dyn InputSource>> =
+        (0..game_mode.player_count()).map(|slot| default_input(game_mode, slot, settings.paddle_control)).collect();
+    let mut recording: Option<Recording> = None;
+
     // For development/testing - enables level jumping with keyboard shortcuts
     let mut dev_mode = true;
+    let mut autopilot = false;
     let mut show_dev_message = false;
     let mut dev_message_timer = 0.0;
 
-    init_blocks(&mut blocks, current_level);
-    balls.push(Ball::new(vec2(screen_width() * 0.5f32, screen_height() * 0.5f32)));
+    level_state.reset(init_blocks(&mut blocks, current_level, game_mode));
+    balls.push(serve_ball(&players[0], 0, run_difficulty));
+
+    // Closing the window saves the run in progress, the achievements and the statistics before quitting
+    prevent_quit();
 
     loop {
+        if is_quit_requested() {
+            if game_mode.saves_runs() && matches!(game_state, GameState::Game | GameState::Paused) {
+                SavedRun::new(SessionSnapshot::new(&blocks, current_level, score, player_lives), run_difficulty, &players[0], &balls, &barrier).save();
+            }
+            achievements.save();
+            statistics.save();
+            break;
+        }
+
         // Level jumping shortcuts for development/testing
-        if dev_mode {
-            // Number keys 1-9 to jump to levels 1-9
-            for i in 1..=9 {
-                if is_key_pressed(match i {
-                    1 => KeyCode::Key1,
-                    2 => KeyCode::Key2,
-                    3 => KeyCode::Key3,
-                    4 => KeyCode::Key4,
-                    5 => KeyCode::Key5,
-                    6 => KeyCode::Key6,
-                    7 => KeyCode::Key7,
-                    8 => KeyCode::Key8,
-                    9 => KeyCode::Key9,
-                    _ => KeyCode::Key0,
-                }) {
+        if dev_mode && game_state != GameState::KeyBindings {
+            // Number keys 1-9 and 0 jump to levels 1-10
+            for i in 1..=10 {
+                if settings.key_bindings.is_pressed(Action::DevLevel(i)) {
                     current_level = i;
                     level_completed = true;
-                    reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut player, current_level, level_completed);
+                    reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
                     game_state = GameState::Game;
                     show_dev_message = true;
                     dev_message_timer = 2.0; // Show message for 2 seconds
                 }
             }
             
-            // 0 key for level 10
-            if is_key_pressed(KeyCode::Key0) {
-                current_level = 10;
-                level_completed = true;
-                reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut player, current_level, level_completed);
-                game_state = GameState::Game;
-                show_dev_message = true;
-                dev_message_timer = 2.0;
-            }
-            
             // Page Up/Down to cycle through levels
-            if is_key_pressed(KeyCode::PageUp) && game_state == GameState::Game {
+            if settings.key_bindings.is_pressed(Action::DevNextLevel) && game_state == GameState::Game {
                 current_level = (current_level + 1).min(20); // Limit to 20 levels for safety
                 level_completed = true;
-                reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut player, current_level, level_completed);
+                reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
                 show_dev_message = true;
                 dev_message_timer = 2.0;
             }
             
-            if is_key_pressed(KeyCode::PageDown) && game_state == GameState::Game {
+            if settings.key_bindings.is_pressed(Action::DevPrevLevel) && game_state == GameState::Game {
                 current_level = (current_level - 1).max(1); // Don't go below level 1
                 level_completed = true;
-                reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut player, current_level, level_completed);
+                reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
                 show_dev_message = true;
                 dev_message_timer = 2.0;
             }
             
+            // F11 toggles the autopilot bot
+            if settings.key_bindings.is_pressed(Action::DevAutopilot) {
+                paddle_inputs[0] = if autopilot {
+                    default_input(game_mode, 0, settings.paddle_control)
+                } else {
+                    Box::new(BotInput::new())
+                };
+                autopilot = !autopilot;
+            }
+
+            // F10 restarts the level and records paddle input, pressing it again restarts the level
+            // from the same seed and plays the recording back
+            if settings.key_bindings.is_pressed(Action::DevRecord) && game_state == GameState::Game {
+                let seed = match &recording {
+                    Some(recording) => recording.seed,
+                    None => rand::rand() as u64,
+                };
+                rand::srand(seed);
+                if let Some(recording) = &recording {
+                    current_level = recording.level;
+                }
+                level_completed = true;
+                reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
+                powerups.clear();
+                match recording.take() {
+                    Some(recording) => {
+                        score = recording.score;
+                        player_lives = recording.lives;
+                        paddle_inputs[0] = Box::new(ReplayInput::new(recording.frames));
+                    }
+                    None => recording = Some(Recording::new(seed, current_level, score, player_lives)),
+                }
+                autopilot = false;
+            }
+
             // Toggle dev mode with F12
-            if is_key_pressed(KeyCode::F12) {
+            if settings.key_bindings.is_pressed(Action::DevToggle) {
                 dev_mode = !dev_mode;
                 show_dev_message = true;
                 dev_message_timer = 2.0;
@@ -718,100 +1243,583 @@ async fn main() {
             }
         }
 
+        // The pause key toggles the pause menu, and play pauses by itself when the browser tab loses focus
+        match game_state {
+            GameState::Game if settings.key_bindings.is_pressed(Action::Pause) || focus::lost_focus() => {
+                // Browsers give no warning before a tab is closed, so pausing also saves the run
+                if game_mode.saves_runs() {
+                    SavedRun::new(SessionSnapshot::new(&blocks, current_level, score, player_lives), run_difficulty, &players[0], &balls, &barrier).save();
+                    has_saved_run = true;
+                }
+                pause_menu.selected = 0;
+                game_state = GameState::Paused;
+            }
+            GameState::Paused if settings.key_bindings.is_pressed(Action::Pause) => {
+                game_state = GameState::Game;
+            }
+            _ => {}
+        }
+
         match game_state {
             GameState::Menu => {
-                if is_key_pressed(KeyCode::Space) {
-                    game_state = GameState::Game;
+                let items = title_items(has_saved_run);
+                title_menu.set_items(title_menu_items(&items));
+                match title_menu.update(&settings.key_bindings).map(|index| items[index]) {
+                    Some(TitleItem::Continue) => match SavedRun::load() {
+                        Some(run) => {
+                            game_mode = GameMode::Classic;
+                            run_difficulty = run.difficulty;
+                            statistics.start_run();
+                            player_scores = vec![0; game_mode.player_count()];
+                            paddle_inputs = (0..game_mode.player_count()).map(|slot| default_input(game_mode, slot, settings.paddle_control)).collect();
+                            autopilot = false;
+                            hot_seat = None;
+                            current_level = run.session.level;
+                            level_completed = true;
+                            reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
+                            run.restore_powerups(&mut players[0], &mut balls, &mut barrier);
+                            blocks = run.session.blocks;
+                            score = run.session.score;
+                            player_lives = run.session.lives;
+                            powerups.clear();
+                            game_state = GameState::Game;
+                        }
+                        None => has_saved_run = false,
+                    },
+                    Some(TitleItem::Play(mode)) => {
+                        // Start a fresh run in the chosen mode, which replaces any saved one
+                        if mode.saves_runs() {
+                            SavedRun::delete();
+                            has_saved_run = false;
+                        }
+                        game_mode = mode;
+                        run_difficulty = settings.difficulty;
+                        statistics.start_run();
+                        player_scores = vec![0; game_mode.player_count()];
+                        paddle_inputs = (0..game_mode.player_count()).map(|slot| default_input(game_mode, slot, settings.paddle_control)).collect();
+                        autopilot = false;
+                        current_level = 1;
+                        level_completed = false;
+                        reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
+                        hot_seat = (game_mode.turn_count() > 1).then(|| {
+                            HotSeat::new(game_mode.turn_count(), &SessionSnapshot::new(&blocks, current_level, score, player_lives))
+                        });
+                        game_state = GameState::Game;
+                    }
+                    Some(TitleItem::HighScores) => {
+                        new_high_score_ranks.clear();
+                        high_score_difficulty = settings.difficulty;
+                        game_state = GameState::HighScores;
+                    }
+                    Some(TitleItem::Achievements) => game_state = GameState::Achievements,
+                    Some(TitleItem::Statistics) => game_state = GameState::Statistics,
+                    Some(TitleItem::Options) => {
+                        options_menu.set_items(options_menu_items(&settings));
+                        options_return_state = GameState::Menu;
+                        game_state = GameState::Options;
+                    }
+                    None => {}
+                }
+            }
+            GameState::Paused => {
+                // Nothing in the game moves until play resumes
+                match pause_menu.update(&settings.key_bindings) {
+                    Some(0) => game_state = GameState::Game,
+                    Some(1) => {
+                        level_completed = true;
+                        reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
+                        powerups.clear();
+                        game_state = GameState::Game;
+                    }
+                    Some(2) => {
+                        options_menu.set_items(options_menu_items(&settings));
+                        options_return_state = GameState::Paused;
+                        game_state = GameState::Options;
+                    }
+                    Some(_) => game_state = GameState::Menu,
+                    None => {}
+                }
+            }
+            GameState::KeyBindings => {
+                if let Some(action) = rebinding {
+                    // The next bindable key pressed becomes the action's only binding, unless it is taken
+                    // by another action on the same screen. Back leaves the binding as it was.
+                    if settings.key_bindings.is_pressed(Action::Back) {
+                        rebinding = None;
+                    } else if let Some(key) = get_last_key_pressed().filter(|key| is_bindable(*key)) {
+                        match settings.key_bindings.rebind(action, key) {
+                            Ok(()) => {
+                                settings.save();
+                                key_bindings_menu.set_items(key_bindings_menu_items(&settings.key_bindings));
+                                rebind_conflict = None;
+                            }
+                            Err(other) => rebind_conflict = Some((key, other)),
+                        }
+                        rebinding = None;
+                    }
+                } else if settings.key_bindings.is_pressed(Action::Back) {
+                    game_state = GameState::Options;
+                } else if let Some(index) = key_bindings_menu.update(&settings.key_bindings) {
+                    match Action::ALL.get(index) {
+                        Some(action) => {
+                            rebinding = Some(*action);
+                            rebind_conflict = None;
+                        }
+                        None => game_state = GameState::Options,
+                    }
+                }
+            }
+            GameState::Options => {
+                // Confirm and right step a setting forwards, left steps it back
+                let step = if settings.key_bindings.is_pressed(Action::MoveLeft) {
+                    -1
+                } else if settings.key_bindings.is_pressed(Action::MoveRight) {
+                    1
+                } else {
+                    0
+                };
+                let changed = if settings.key_bindings.is_pressed(Action::Back) {
+                    game_state = options_return_state;
+                    None
+                } else if let Some(index) = options_menu.update(&settings.key_bindings) {
+                    match OPTION_ITEMS[index] {
+                        OptionItem::KeyBindings => {
+                            key_bindings_menu.set_items(key_bindings_menu_items(&settings.key_bindings));
+                            game_state = GameState::KeyBindings;
+                            None
+                        }
+                        OptionItem::Back => {
+                            game_state = options_return_state;
+                            None
+                        }
+                        item => Some((item, 1)),
+                    }
+                } else if step != 0 {
+                    Some((OPTION_ITEMS[options_menu.selected], step))
+                } else {
+                    None
+                };
+
+                if let Some((item, step)) = changed {
+                    change_option(&mut settings, item, step);
+                    settings.apply(&mut audio_manager, &mut texture_manager);
+                    settings.save();
+                    options_menu.set_items(options_menu_items(&settings));
+                    match item {
+                        OptionItem::Fullscreen => set_fullscreen(settings.fullscreen),
+                        OptionItem::PaddleControl if !autopilot => {
+                            paddle_inputs[0] = default_input(game_mode, 0, settings.paddle_control);
+                        }
+                        _ => {}
+                    }
                 }
             }
             GameState::Game => {
-                player.update(get_frame_time());
-                
+                // Replays dictate the length of each step so they play out exactly as recorded
+                let dt = paddle_inputs[0].step_time().unwrap_or_else(get_frame_time);
+                // Every mode keeps the level's time for the clear time bonus, time attack also shows it
+                level_state.timer.update(dt);
+                extra_life_flash = (extra_life_flash - dt).max(0f32);
+                statistics.add_play_time(dt);
+                if game_mode == GameMode::Puzzle && balls.iter().any(|ball| ball.is_stuck()) {
+                    level_state.puzzle_run.update_aim(&settings.key_bindings, dt);
+                }
+                let drop_rules = DropRules::new(game_mode, run_difficulty, level_state.puzzle_run.aim);
+
+                for (slot, player) in players.iter_mut().enumerate() {
+                    let intent = paddle_inputs[slot].poll(&InputContext {
+                        dt,
+                        paddle: player.rect,
+                        balls: &balls,
+                        bindings: &settings.key_bindings,
+                    });
+                    if slot == 0 && let Some(recording) = recording.as_mut() {
+                        recording.frames.push((dt, intent));
+                    }
+                    if paddle_inputs[slot].is_finished() {
+                        paddle_inputs[slot] = default_input(game_mode, slot, settings.paddle_control);
+                    }
+                    player.update(dt, &intent, settings.pointer_max_speed);
+
+                    // Launching releases any balls held on this paddle, otherwise it fires the laser
+                    if intent.launch {
+                        if balls.iter().any(|ball| ball.is_stuck() && ball.owner == slot) {
+                            for ball in balls.iter_mut().filter(|ball| ball.owner == slot) {
+                                if game_mode == GameMode::Puzzle {
+                                    ball.launch_at(level_state.puzzle_run.aim, player.facing);
+                                } else {
+                                    ball.launch(&player.rect, player.facing, player.spin());
+                                }
+                            }
+                            if game_mode == GameMode::Puzzle {
+                                level_state.puzzle_run.launches_used += 1;
+                            }
+                        } else if let Some(cannons) = player.fire_laser() {
+                            for cannon in cannons {
+                                lasers.push(Laser::new(cannon, slot, player.facing));
+                            }
+                            audio_manager.play_sound_effect("laser_shot");
+                        }
+                    }
+                }
+
+                let walls = wall_colliders(game_mode.has_ceiling());
                 for ball in balls.iter_mut() {
-                    // Store position before update to detect wall collisions
-                    let prev_x = ball.rect.x;
-                    let prev_y = ball.rect.y;
-                    let prev_right = ball.rect.x + ball.rect.w;
-                    
+                    if ball.is_stuck() {
+                        let paddle = &players[ball.owner];
+                        ball.follow_paddle(&paddle.rect, paddle.facing);
+                        continue;
+                    }
+
                     // Update ball position
-                    ball.update(get_frame_time());
-                    
-                    // Detect wall collisions more reliably
-                    let hit_left_wall = prev_x > 0.0 && ball.rect.x <= 0.01;
-                    let hit_right_wall = prev_right < screen_width() && ball.rect.x + ball.rect.w >= screen_width() - 0.01;
-                    let hit_ceiling = prev_y > 0.0 && ball.rect.y <= 0.01;
-                    
-                    if hit_left_wall || hit_right_wall || hit_ceiling {
-                        audio_manager.play_sound_effect("wall_hit");
+                    ball.update(dt);
+
+                    for wall in walls.iter() {
+                        if resolve_collision(ball, wall) {
+                            audio_manager.play_sound_effect(collision_sound(wall.kind));
+                        }
                     }
                 }
 
                 let mut spawn_later = vec![];
                 for ball in balls.iter_mut() {
-                    if resolve_collision(&mut ball.rect, &mut ball.vel, &player.rect) {
-                        audio_manager.play_sound_effect("bounce");
+                    if ball.is_stuck() {
+                        continue;
+                    }
+                    for (slot, player) in players.iter().enumerate() {
+                        let paddle = player.collider();
+                        if resolve_collision(ball, &paddle) {
+                            audio_manager.play_sound_effect(collision_sound(paddle.kind));
+                            // The last paddle to touch the ball gets the credit for what it breaks
+                            ball.owner = slot;
+                            level_state.score.paddle_hit();
+                            events.push(GameEvent::PaddleHit);
+                            // Only hits on the playing side pick up spin or get caught
+                            if ball.vel.y * player.facing > 0f32 {
+                                ball.add_spin(player.spin());
+                                // A sticky paddle holds on to the ball
+                                if player.has_catch() {
+                                    ball.catch(&player.rect, player.facing);
+                                }
+                            }
+                        }
                     }
                     for block in blocks.iter_mut() {
-                        if resolve_collision(&mut ball.rect, &mut ball.vel, &block.rect) {
-                            block.lives -= 1;
-                            audio_manager.play_sound_effect("block_hit");
-                            if block.lives <= 0 {
-                                score += 10;
-                                audio_manager.play_sound_effect("block_destroyed");
-                                if block.block_type == BlockType::SpawnBallOnDeath {
-                                    spawn_later.push(Ball::new(ball.rect.point()));
-                                } else if block.block_type == BlockType::SpawnPowerup {
-                                    powerups.push(Powerup::new(block.rect.point()));
+                        if ball.is_fireball() && block.is_destructible() {
+                            // Fireballs plough straight through anything breakable
+                            if block.lives > 0 && ball.rect.overlaps(&block.rect) {
+                                let lives = block.lives;
+                                let points = level_state.score.block_hit(damage_block(block, lives, ball.owner, -players[ball.owner].facing, ball.rect.point(), &drop_rules, &mut spawn_later, &mut powerups, &mut events, &audio_manager));
+                                if add_points(points, ball.owner, &mut score, &mut player_scores, &mut player_lives, &settings.extra_lives, game_mode, &audio_manager) {
+                                    extra_life_flash = EXTRA_LIFE_FLASH;
                                 }
                             }
+                        } else if resolve_collision(ball, &block.collider()) {
+                            let points = level_state.score.block_hit(damage_block(block, 1, ball.owner, -players[ball.owner].facing, ball.rect.point(), &drop_rules, &mut spawn_later, &mut powerups, &mut events, &audio_manager));
+                            if add_points(points, ball.owner, &mut score, &mut player_scores, &mut player_lives, &settings.extra_lives, game_mode, &audio_manager) {
+                                extra_life_flash = EXTRA_LIFE_FLASH;
+                            }
                         }
                     }
                 }
-                for ball in spawn_later.into_iter() {
-                    balls.push(ball);
+
+                // Each laser damages the first live block in its path and is consumed by it
+                for laser in lasers.iter_mut() {
+                    laser.update(dt);
+                }
+                lasers.retain(|laser| {
+                    if laser.is_offscreen() {
+                        return false;
+                    }
+                    let target = blocks
+                        .iter_mut()
+                        .filter(|block| block.lives > 0 && block.rect.overlaps(&laser.rect))
+                        .max_by(|a, b| (a.rect.y * -laser.dir).total_cmp(&(b.rect.y * -laser.dir)));
+                    match target {
+                        Some(block) => {
+                            let spawn_pos = block.rect.center();
+                            let points = level_state.score.block_hit(damage_block(block, 1, laser.owner, -laser.dir, spawn_pos, &drop_rules, &mut spawn_later, &mut powerups, &mut events, &audio_manager));
+                            if add_points(points, laser.owner, &mut score, &mut player_scores, &mut player_lives, &settings.extra_lives, game_mode, &audio_manager) {
+                                extra_life_flash = EXTRA_LIFE_FLASH;
+                            }
+                            false
+                        }
+                        None => true,
+                    }
+                });
+                if !spawn_later.is_empty() {
+                    balls.extend(spawn_later);
+                    events.push(GameEvent::BallsInPlay(balls.len()));
+                }
+                if level_state.score.multiplier() > 1 {
+                    events.push(GameEvent::ComboMultiplier(level_state.score.multiplier()));
                 }
 
+                let magnet_player = players.iter().find(|player| player.has_magnet());
                 for powerup in powerups.iter_mut() {
-                    powerup.update(get_frame_time());
+                    powerup.update(dt);
+                    if let Some(player) = magnet_player {
+                        powerup.attract(player.rect.center().x, dt);
+                    }
                 }
-                handle_powerup_collision(&mut player, &mut powerups, &audio_manager);
+                powerups.retain(|powerup| !powerup.is_offscreen());
+                handle_powerup_collision(&mut players, &mut balls, &mut barrier, &mut powerups, &mut events, &audio_manager);
 
-                let balls_len = balls.len();
-                balls.retain(|ball| ball.rect.y < screen_height());
-                let removed_balls = balls_len - balls.len();
-                if removed_balls > 0 && balls.is_empty() {
+                // The barrier saves one ball, then shatters
+                if let Some(active_barrier) = barrier.as_mut() {
+                    active_barrier.update(dt);
+                    if balls.iter_mut().any(|ball| active_barrier.bounce(&mut ball.rect, &mut ball.vel)) {
+                        particles.extend(active_barrier.shatter());
+                        audio_manager.play_sound_effect("barrier_break");
+                        barrier = None;
+                    } else if active_barrier.is_expired() {
+                        barrier = None;
+                    }
+                }
+
+                let missed: Vec<usize> = balls.iter().filter_map(|ball| missed_by(ball, &players, game_mode)).collect();
+                balls.retain(|ball| missed_by(ball, &players, game_mode).is_none());
+                for _ in missed.iter() {
+                    level_state.score.ball_lost();
+                    events.push(GameEvent::BallLost);
+                }
+                if game_mode == GameMode::Versus {
+                    // Every ball that gets past a paddle costs its player, and they serve the next one
+                    for slot in missed.iter() {
+                        add_points(-VERSUS_MISS_PENALTY, *slot, &mut score, &mut player_scores, &mut player_lives, &settings.extra_lives, game_mode, &audio_manager);
+                        audio_manager.play_sound_effect("life_lost");
+                    }
+                    if let Some(slot) = missed.last()
+                        && balls.is_empty()
+                    {
+                        balls.push(serve_ball(&players[*slot], *slot, run_difficulty));
+                    }
+                } else if game_mode == GameMode::TimeAttack {
+                    // Losing the last ball costs time instead of a life
+                    if let Some(owner) = missed.last()
+                        && balls.is_empty()
+                    {
+                        level_state.timer.add_penalty();
+                        audio_manager.play_sound_effect("life_lost");
+                        balls.push(serve_ball(&players[*owner], *owner, run_difficulty));
+                    }
+                } else if game_mode == GameMode::Puzzle {
+                    // The next ball is only served while there are launches left
+                    if let Some(owner) = missed.last()
+                        && balls.is_empty()
+                    {
+                        audio_manager.play_sound_effect("life_lost");
+                        if level_state.puzzle_run.launches_left(puzzle(current_level)) > 0 {
+                            balls.push(serve_ball(&players[*owner], *owner, run_difficulty));
+                        } else {
+                            game_state = GameState::PuzzleFailed;
+                        }
+                    }
+                } else if let Some(owner) = missed.last()
+                    && balls.is_empty()
+                {
+                    // Lives are shared, the new ball is served by whoever lost the last one
                     player_lives -= 1;
                     audio_manager.play_sound_effect("life_lost");
-                    balls.push(Ball::new(player.rect.point() + vec2(player.rect.w * 0.5f32 + BALL_SIZE * 0.5f32, -50f32)));
-                    if player_lives <= 0 {
+                    balls.push(serve_ball(&players[*owner], *owner, run_difficulty));
+                    if let Some(seats) = hot_seat.as_mut() {
+                        // Losing a life hands the paddle to the next player who still has lives
+                        let previous_turn = seats.turn;
+                        match seats.swap_turn(SessionSnapshot::new(&blocks, current_level, score, player_lives)) {
+                            Some(session) => {
+                                blocks = session.blocks;
+                                current_level = session.level;
+                                score = session.score;
+                                player_lives = session.lives;
+                                if seats.turn != previous_turn {
+                                    level_state.reset(level_par_times(current_level, game_mode));
+                                    players = create_players(game_mode, run_difficulty);
+                                    balls.clear();
+                                    balls.push(serve_ball(&players[0], 0, run_difficulty));
+                                    lasers.clear();
+                                    powerups.clear();
+                                    barrier = None;
+                                    game_state = GameState::NextTurn;
+                                }
+                            }
+                            None => game_state = GameState::Dead,
+                        }
+                    } else if player_lives <= 0 {
+                        // The run is over, so there is nothing left to continue
+                        if game_mode.saves_runs() {
+                            SavedRun::delete();
+                            has_saved_run = false;
+                        }
                         game_state = GameState::Dead;
                     }
                 }
 
                 blocks.retain(|block| block.lives > 0);
-                if blocks.is_empty() {
-                    game_state = GameState::LevelCompleted;
+                if game_mode == GameMode::Survival {
+                    let (block_size, start_x) = board_geometry(SURVIVAL_COLUMNS);
+                    if let Some(y) = level_state.survival.update(dt, block_size.y + BOARD_PADDING, BOARD_TOP, &mut blocks) {
+                        blocks.extend(generate_row(start_x, y, block_size, BOARD_PADDING, level_state.survival.rows_added));
+                    }
+                    // The run is over once the blocks reach the paddle line
+                    if game_state == GameState::Game && blocks.iter().any(|block| block.rect.bottom() >= players[0].rect.y) {
+                        audio_manager.play_sound_effect("life_lost");
+                        game_state = GameState::Dead;
+                    }
+                } else if !blocks.iter().any(|block| block.is_destructible()) {
+                    if game_mode == GameMode::TimeAttack {
+                        let time = level_state.timer.total();
+                        let new_best = best_times.record((run_difficulty, current_level), time);
+                        if new_best {
+                            best_times.save();
+                        }
+                        level_result = Some((time, new_best));
+                    }
+                    if game_mode != GameMode::Versus {
+                        events.push(GameEvent::LevelCleared { level: current_level, flawless: level_state.score.is_flawless() });
+                        let breakdown = level_state.score.breakdown(level_state.timer.total(), level_state.par_times.par);
+                        // The whole board earned the bonus, so it is shared out evenly between the players
+                        let shares = player_scores.len() as i32;
+                        for slot in 0..player_scores.len() {
+                            let share = breakdown.bonus() / shares + if slot == 0 { breakdown.bonus() % shares } else { 0 };
+                            if add_points(share, slot, &mut score, &mut player_scores, &mut player_lives, &settings.extra_lives, game_mode, &audio_manager) {
+                                extra_life_flash = EXTRA_LIFE_FLASH;
+                            }
+                        }
+                        level_breakdown = Some(breakdown);
+                    }
+                    if game_mode == GameMode::Puzzle {
+                        let stars = puzzle(current_level).stars(level_state.puzzle_run.launches_used);
+                        let new_best = puzzle_stars.record(current_level, stars);
+                        if new_best {
+                            puzzle_stars.save();
+                        }
+                        puzzle_result = Some((stars, new_best));
+                    }
+                    // Save straight away so the cleared level counts even if the game is closed on the
+                    // level end screen. The run continues on a fresh board of the next level.
+                    if game_mode.saves_runs() {
+                        let mut next_blocks = Vec::new();
+                        init_blocks(&mut next_blocks, current_level + 1, game_mode);
+                        let fresh_players = create_players(game_mode, run_difficulty);
+                        SavedRun::new(SessionSnapshot::new(&next_blocks, current_level + 1, score, player_lives), run_difficulty, &fresh_players[0], &[], &None).save();
+                        has_saved_run = true;
+                    }
+                    // A versus match is over once the shared board is cleared
+                    game_state = if game_mode == GameMode::Versus {
+                        GameState::MatchOver
+                    } else {
+                        GameState::LevelCompleted
+                    };
                     audio_manager.play_sound_effect("level_completed");
                 }
             }
             GameState::LevelCompleted => {
-                if is_key_pressed(KeyCode::Space) {
-                    current_level += 1;
+                if is_action_pressed(&settings.key_bindings, Action::Confirm) {
+                    if game_mode == GameMode::Puzzle && current_level >= PUZZLES.len() {
+                        // Every puzzle is solved
+                        game_state = GameState::Menu;
+                    } else {
+                        current_level += 1;
+                        level_completed = true;
+                        reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
+                        if game_mode.saves_runs() {
+                            SavedRun::new(SessionSnapshot::new(&blocks, current_level, score, player_lives), run_difficulty, &players[0], &balls, &barrier).save();
+                            has_saved_run = true;
+                        }
+                        game_state = GameState::Game;
+                    }
+                }
+            }
+            GameState::PuzzleFailed => {
+                if is_action_pressed(&settings.key_bindings, Action::Confirm) {
                     level_completed = true;
-                    reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut player, current_level, level_completed);
+                    reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
+                    powerups.clear();
+                    game_state = GameState::Game;
+                }
+            }
+            GameState::MatchOver => {
+                if is_action_pressed(&settings.key_bindings, Action::Confirm) {
                     game_state = GameState::Menu;
                 }
             }
+            GameState::NextTurn => {
+                if is_action_pressed(&settings.key_bindings, Action::Confirm) {
+                    game_state = GameState::Game;
+                }
+            }
             GameState::Dead => {
-                if is_key_pressed(KeyCode::Space) {
+                if is_action_pressed(&settings.key_bindings, Action::Confirm) {
+                    // Every player whose final score made the table gets to enter their initials.
+                    // Survival has no levels, so its entries record the rows survived instead.
+                    let progress = if game_mode == GameMode::Survival { level_state.survival.rows_added } else { current_level };
+                    let final_scores: Vec<(i32, usize)> = match &hot_seat {
+                        Some(seats) => seats.sessions.iter().map(|session| (session.score, session.level)).collect(),
+                        None => vec![(score, progress)],
+                    };
+                    pending_high_scores = final_scores
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(_, (final_score, _))| high_score_table.qualifies(*final_score, run_difficulty))
+                        .map(|(player, (final_score, level))| (player, HighScore::new(final_score, level, game_mode.label(), run_difficulty)))
+                        .collect();
+                    new_high_score_ranks.clear();
+
                     current_level = 1;
                     level_completed = false;
-                    reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut player, current_level, level_completed);
+                    reset_game(&mut score, &mut player_lives, &mut blocks, &mut balls, &mut lasers, &mut barrier, &mut players, &mut level_state, game_mode, run_difficulty, current_level, level_completed);
+                    name_entry = NameEntry::new();
+                    game_state = if pending_high_scores.is_empty() { GameState::Menu } else { GameState::EnterName };
+                }
+            }
+            GameState::EnterName => {
+                if name_entry.update(&settings.key_bindings, is_action_pressed(&settings.key_bindings, Action::Confirm)) {
+                    let (_, mut entry) = pending_high_scores.remove(0);
+                    entry.name = name_entry.name();
+                    high_score_difficulty = entry.difficulty;
+                    if let Some(rank) = high_score_table.insert(entry) {
+                        // Entries of the same run already in the table at or below it move down a place
+                        for other in new_high_score_ranks.iter_mut().filter(|other| **other >= rank) {
+                            *other += 1;
+                        }
+                        new_high_score_ranks.retain(|other| *other < MAX_HIGH_SCORES);
+                        new_high_score_ranks.push(rank);
+                    }
+                    high_score_table.save();
+                    name_entry = NameEntry::new();
+                    if pending_high_scores.is_empty() {
+                        game_state = GameState::HighScores;
+                    }
+                }
+            }
+            GameState::Achievements | GameState::Statistics => {
+                if is_action_pressed(&settings.key_bindings, Action::Confirm) || settings.key_bindings.is_pressed(Action::Back) {
+                    game_state = GameState::Menu;
+                }
+            }
+            GameState::HighScores => {
+                let step = settings.key_bindings.is_pressed(Action::MoveRight) as i32 - settings.key_bindings.is_pressed(Action::MoveLeft) as i32;
+                if step != 0 {
+                    high_score_difficulty = cycle(&Difficulty::ALL, high_score_difficulty, step);
+                    new_high_score_ranks.clear();
+                }
+                if is_action_pressed(&settings.key_bindings, Action::Confirm) || settings.key_bindings.is_pressed(Action::Back) {
                     game_state = GameState::Menu;
                 }
             }
         }
 
+        let mut save_progress = false;
+        for event in events.drain(..) {
+            save_progress |= statistics.handle(&event);
+            save_progress |= achievements.handle(&event, &statistics.lifetime);
+        }
+        if save_progress {
+            achievements.save();
+            statistics.save();
+        }
+        achievements.update(get_frame_time());
+
         if let Some(bg_texture) = &texture_manager.background_texture {
             draw_texture_ex(
                 &bg_texture,
@@ -827,7 +1835,9 @@ async fn main() {
             clear_background(Color::new(0.1, 0.1, 0.2, 1.0));
         }
 
-        player.draw(&texture_manager);
+        for player in players.iter() {
+            player.draw(&texture_manager);
+        }
         for block in blocks.iter() {
             block.draw(&texture_manager);
         }
@@ -837,13 +1847,46 @@ async fn main() {
         for powerup in powerups.iter() {
             powerup.draw(&texture_manager);
         }
+        for laser in lasers.iter() {
+            laser.draw();
+        }
+        if let Some(barrier) = &barrier {
+            barrier.draw();
+        }
+        // Particles are purely cosmetic, so they keep animating outside of gameplay unless paused
+        for particle in particles.iter_mut() {
+            if game_state != GameState::Paused {
+                particle.update(get_frame_time());
+            }
+            particle.draw();
+        }
+        particles.retain(|particle| particle.is_alive());
 
         match game_state {
             GameState::Menu => {
-                draw_title_text("Press SPACE to start", &font);
+                title_menu.draw("Rustanoid", &font);
+            }
+            GameState::Paused => {
+                draw_rectangle(0.0, 0.0, screen_width(), screen_height(), Color::new(0.0, 0.0, 0.0, 0.4));
+                pause_menu.draw("Paused", &font);
+            }
+            GameState::Options => {
+                options_menu.draw("Options", &font);
+            }
+            GameState::KeyBindings => {
+                let title = match (rebinding, rebind_conflict) {
+                    (Some(action), _) => format!("Press a key for {}", action.label()),
+                    (None, Some((key, other))) => format!("{} is taken by {}", key_name(key), other.label()),
+                    (None, None) => "Key bindings".to_string(),
+                };
+                key_bindings_menu.draw(&title, &font);
             }
             GameState::Game => {
-                let score_text = format!("score: {}", score);
+                let score_text = if game_mode == GameMode::Versus {
+                    format!("{} : {}", player_scores[0], player_scores[1])
+                } else {
+                    format!("score: {}", score)
+                };
                 let score_text_dim = measure_text(&score_text, Some(&font), 30u16, 1.0);
                 draw_text_ex(
                     &score_text,
@@ -852,14 +1895,81 @@ async fn main() {
                     TextParams { font: Some(&font), font_size: 30u16, color: BLACK, ..Default::default() },
                 );
 
+                if game_mode.has_lives() {
+                    // The lives blink for a while after an extra life
+                    let lives_color = if extra_life_flash > 0f32 && (extra_life_flash * 8f32) as i32 % 2 == 0 { GOLD } else { BLACK };
+                    draw_text_ex(
+                        &format!("lives: {}", player_lives),
+                        30.0,
+                        40.0,
+                        TextParams { font: Some(&font), font_size: 30u16, color: lives_color, ..Default::default() },
+                    );
+                }
+                if extra_life_flash > 0f32 {
+                    let extra_life_text = "Extra life!";
+                    let extra_life_text_dim = measure_text(extra_life_text, Some(&font), 40u16, 1.0);
+                    draw_text_ex(
+                        extra_life_text,
+                        screen_width() * 0.5f32 - extra_life_text_dim.width * 0.5f32,
+                        screen_height() * 0.5f32,
+                        TextParams { font: Some(&font), font_size: 40u16, color: GOLD, ..Default::default() },
+                    );
+                }
+
+                if game_mode == GameMode::TimeAttack {
+                    draw_text_ex(
+                        &format!("time: {}", format_time(level_state.timer.total())),
+                        30.0,
+                        40.0,
+                        TextParams { font: Some(&font), font_size: 30u16, color: BLACK, ..Default::default() },
+                    );
+                    let times = level_state.par_times;
+                    draw_text_ex(
+                        &format!("par {}  gold {}", format_time(times.par), format_time(times.gold)),
+                        30.0,
+                        64.0,
+                        TextParams { font: Some(&font), font_size: 20u16, color: BLACK, ..Default::default() },
+                    );
+                }
+
+                if game_mode == GameMode::Puzzle {
+                    let current_puzzle = puzzle(current_level);
+                    draw_text_ex(
+                        &format!("launches: {}", level_state.puzzle_run.launches_left(current_puzzle)),
+                        30.0,
+                        40.0,
+                        TextParams { font: Some(&font), font_size: 30u16, color: BLACK, ..Default::default() },
+                    );
+                    draw_text_ex(
+                        &format!("par {}", current_puzzle.par),
+                        30.0,
+                        64.0,
+                        TextParams { font: Some(&font), font_size: 20u16, color: BLACK, ..Default::default() },
+                    );
+                    // Where the held ball will go
+                    for ball in balls.iter().filter(|ball| ball.is_stuck()) {
+                        let start = ball.rect.center();
+                        let angle = level_state.puzzle_run.aim.to_radians();
+                        let end = start + vec2(angle.sin(), -angle.cos()) * 150f32;
+                        draw_line(start.x, start.y, end.x, end.y, 3f32, Color::new(1.0, 1.0, 1.0, 0.7));
+                    }
+                }
+
+                let difficulty_text = run_difficulty.label();
+                let difficulty_text_dim = measure_text(difficulty_text, Some(&font), 20u16, 1.0);
                 draw_text_ex(
-                    &format!("lives: {}", player_lives),
-                    30.0,
-                    40.0,
-                    TextParams { font: Some(&font), font_size: 30u16, color: BLACK, ..Default::default() },
+                    difficulty_text,
+                    screen_width() - difficulty_text_dim.width - 30.0,
+                    64.0,
+                    TextParams { font: Some(&font), font_size: 20u16, color: BLACK, ..Default::default() },
                 );
 
-                let level_text = format!("Level: {}", current_level);
+                let level_text = match &hot_seat {
+                    Some(seats) => format!("P{} Level: {}", seats.turn + 1, current_level),
+                    None if game_mode == GameMode::Survival => format!("Rows: {}", level_state.survival.rows_added),
+                    None if game_mode == GameMode::Puzzle => format!("Puzzle {}: {}", current_level, puzzle(current_level).name),
+                    None => format!("Level: {}", current_level),
+                };
                 let level_text_dim = measure_text(&level_text, Some(&font), 30u16, 1.0);
                 draw_text_ex(
                     &level_text,
@@ -868,6 +1978,52 @@ async fn main() {
                     TextParams { font: Some(&font), font_size: 30u16, color: BLACK, ..Default::default()},
                 );
 
+                if level_state.score.multiplier() > 1 {
+                    let combo_text = format!("combo x{}", level_state.score.multiplier());
+                    let combo_text_dim = measure_text(&combo_text, Some(&font), 20u16, 1.0);
+                    draw_text_ex(
+                        &combo_text,
+                        screen_width() * 0.5f32 - combo_text_dim.width * 0.5f32,
+                        64.0,
+                        TextParams { font: Some(&font), font_size: 20u16, color: BLACK, ..Default::default() },
+                    );
+                }
+
+                // Each player's contribution, marked with their paddle colour
+                if players.len() > 1 {
+                    let mut x = 30.0;
+                    for (slot, player) in players.iter().enumerate() {
+                        let contribution_text = format!("P{}: {}", slot + 1, player_scores[slot]);
+                        draw_rectangle(x, 52.0, 12.0, 12.0, player.color);
+                        draw_text_ex(
+                            &contribution_text,
+                            x + 18.0,
+                            64.0,
+                            TextParams { font: Some(&font), font_size: 20u16, color: BLACK, ..Default::default() },
+                        );
+                        x += measure_text(&contribution_text, Some(&font), 20u16, 1.0).width + 48.0;
+                    }
+                }
+
+                if balls.iter().any(|ball| ball.is_stuck()) {
+                    let launch_text = if game_mode == GameMode::Puzzle {
+                        format!(
+                            "Aim with {} and {}, then launch",
+                            settings.key_bindings.describe(Action::AimLeft),
+                            settings.key_bindings.describe(Action::AimRight)
+                        )
+                    } else {
+                        "Press the launch key or tap to launch".to_string()
+                    };
+                    let launch_text_dim = measure_text(&launch_text, Some(&font), 24u16, 1.0);
+                    draw_text_ex(
+                        &launch_text,
+                        screen_width() * 0.5f32 - launch_text_dim.width * 0.5f32,
+                        screen_height() - 20.0,
+                        TextParams { font: Some(&font), font_size: 24u16, color: BLACK, ..Default::default() },
+                    );
+                }
+
                 // Show dev mode message if enabled
                 if dev_mode && show_dev_message {
                     let dev_message = format!("Dev Mode: Level {}", current_level);
@@ -882,11 +2038,108 @@ async fn main() {
             }
             GameState::LevelCompleted => {
                 draw_title_text(&format!("Level {} Completed!", current_level), &font);
+                let mut lines: Vec<String> = Vec::new();
+                if game_mode == GameMode::TimeAttack
+                    && let Some((time, new_best)) = level_result
+                {
+                    let times = level_state.par_times;
+                    let mut result_text = format!("{} - {}", format_time(time), times.rating(time));
+                    if new_best {
+                        result_text.push_str(", new best!");
+                    }
+                    lines.push(result_text);
+                    lines.push(format!(
+                        "Par {}  Gold {}  Best {}",
+                        format_time(times.par),
+                        format_time(times.gold),
+                        format_time(best_times.best((run_difficulty, current_level)).unwrap_or(time)),
+                    ));
+                }
+                if game_mode == GameMode::Puzzle
+                    && let Some((stars, new_best)) = puzzle_result
+                {
+                    let current_puzzle = puzzle(current_level);
+                    let mut stars_text = format!("{} of 3 stars", stars);
+                    if new_best {
+                        stars_text.push_str(", new best!");
+                    }
+                    lines.push(stars_text);
+                    lines.push(format!(
+                        "Launches {}  Par {}  Best {} stars",
+                        level_state.puzzle_run.launches_used,
+                        current_puzzle.par,
+                        puzzle_stars.best(current_level).unwrap_or(stars),
+                    ));
+                }
+                if let Some(breakdown) = &level_breakdown {
+                    lines.push(format!("Blocks: {}", breakdown.blocks));
+                    lines.push(format!("Combos (best x{}): {}", breakdown.best_multiplier, breakdown.combo));
+                    lines.push(format!("Clear time bonus: {}", breakdown.clear_time));
+                    lines.push(format!("No miss bonus: {}", breakdown.no_miss));
+                    lines.push(format!("Level total: {}", breakdown.total()));
+                }
+                for (i, text) in lines.iter().enumerate() {
+                    let dims = measure_text(text, Some(&font), 24u16, 1.0);
+                    draw_text_ex(
+                        text,
+                        screen_width() * 0.5f32 - dims.width * 0.5f32,
+                        screen_height() * 0.5f32 + 40.0 + i as f32 * 30.0,
+                        TextParams { font: Some(&font), font_size: 24u16, color: BLACK, ..Default::default() },
+                    );
+                }
+            }
+            GameState::PuzzleFailed => {
+                draw_title_text("Out of launches! Confirm to retry", &font);
+            }
+            GameState::MatchOver => {
+                let result = match player_scores[0].cmp(&player_scores[1]) {
+                    std::cmp::Ordering::Greater => "Player 1 wins!".to_string(),
+                    std::cmp::Ordering::Less => "Player 2 wins!".to_string(),
+                    std::cmp::Ordering::Equal => "It's a draw!".to_string(),
+                };
+                draw_title_text(&format!("{} {} - {}", result, player_scores[0], player_scores[1]), &font);
+            }
+            GameState::NextTurn => {
+                if let Some(seats) = &hot_seat {
+                    draw_title_text(&format!("Player {}, get ready!", seats.turn + 1), &font);
+                }
             }
             GameState::Dead => {
-                draw_title_text(&format!("Game over. Your score: {}", score), &font);
+                match &hot_seat {
+                    Some(seats) => {
+                        let scores: Vec<String> = seats
+                            .sessions
+                            .iter()
+                            .enumerate()
+                            .map(|(player, session)| format!("P{}: {}", player + 1, session.score))
+                            .collect();
+                        draw_title_text(&format!("Game over. {}", scores.join("  ")), &font);
+                    }
+                    None => draw_title_text(&format!("Game over. Your score: {}", score), &font),
+                }
+                statistics.draw_run_summary(&font);
+            }
+            GameState::EnterName => {
+                if let Some((player, entry)) = pending_high_scores.first() {
+                    let title = if game_mode.turn_count() > 1 {
+                        format!("Player {}: new high score {}!", player + 1, entry.score)
+                    } else {
+                        format!("New high score {}!", entry.score)
+                    };
+                    name_entry.draw(&title, &font);
+                }
+            }
+            GameState::HighScores => {
+                high_score_table.draw(&font, high_score_difficulty, &new_high_score_ranks);
+            }
+            GameState::Achievements => {
+                achievements.draw_gallery(&font, &statistics.lifetime);
+            }
+            GameState::Statistics => {
+                statistics.draw(&font);
             }
         }
+        achievements.draw_toast(&font);
 
         next_frame().await
     }
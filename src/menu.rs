@@ -0,0 +1,87 @@
+use macroquad::prelude::*;
+use crate::input::{is_pointer_pressed, Action, KeyBindings};
+
+const ITEM_FONT_SIZE: u16 = 32;
+const ITEM_HEIGHT: f32 = 44f32;
+
+// Vertical list of selectable items driven by the menu actions, mouse or touch
+pub struct Menu {
+    pub items: Vec<String>,
+    pub selected: usize,
+}
+
+impl Menu {
+    pub fn new(items: &[&str]) -> Self {
+        Self {
+            items: items.iter().map(|item| item.to_string()).collect(),
+            selected: 0,
+        }
+    }
+
+    pub fn set_items(&mut self, items: Vec<String>) {
+        self.items = items;
+        self.selected = self.selected.min(self.items.len().saturating_sub(1));
+    }
+
+    // Returns the index of the item chosen this frame, if any
+    pub fn update(&mut self, bindings: &KeyBindings) -> Option<usize> {
+        if self.items.is_empty() {
+            return None;
+        }
+        if bindings.is_pressed(Action::MenuUp) {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+        if bindings.is_pressed(Action::MenuDown) {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+        if bindings.is_pressed(Action::Confirm) {
+            return Some(self.selected);
+        }
+        if is_pointer_pressed() {
+            let pointer = touches().first().map(|touch| touch.position).unwrap_or(Vec2::from(mouse_position()));
+            if let Some(index) = (0..self.items.len()).find(|i| self.item_rect(*i).contains(pointer)) {
+                self.selected = index;
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    // Long menus shrink their items so they still fit on the screen
+    fn item_height(&self) -> f32 {
+        ITEM_HEIGHT.min(screen_height() * 0.75f32 / self.items.len().max(1) as f32)
+    }
+
+    fn item_rect(&self, index: usize) -> Rect {
+        let item_height = self.item_height();
+        let total_height = self.items.len() as f32 * item_height;
+        let top = screen_height() * 0.5f32 - total_height * 0.5f32 + 40f32;
+        Rect::new(screen_width() * 0.2f32, top + index as f32 * item_height, screen_width() * 0.6f32, item_height)
+    }
+
+    pub fn draw(&self, title: &str, font: &Font) {
+        let title_dims = measure_text(title, Some(font), 50u16, 1.0f32);
+        let first_item = self.item_rect(0);
+        draw_text_ex(
+            title,
+            screen_width() * 0.5f32 - title_dims.width * 0.5f32,
+            first_item.y - 30f32,
+            TextParams { font: Some(font), font_size: 50u16, color: BLACK, ..Default::default() },
+        );
+
+        let font_size = ITEM_FONT_SIZE.min((self.item_height() * 0.7f32) as u16);
+        for (i, item) in self.items.iter().enumerate() {
+            let rect = self.item_rect(i);
+            if i == self.selected {
+                draw_rectangle(rect.x, rect.y, rect.w, rect.h, Color::new(1.0, 1.0, 1.0, 0.5));
+            }
+            let dims = measure_text(item, Some(font), font_size, 1.0f32);
+            draw_text_ex(
+                item,
+                screen_width() * 0.5f32 - dims.width * 0.5f32,
+                rect.y + rect.h * 0.5f32 + dims.offset_y * 0.5f32,
+                TextParams { font: Some(font), font_size, color: BLACK, ..Default::default() },
+            );
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::game_objects::ball::LAUNCH_MAX_ANGLE;
+use crate::game_objects::block::BlockType;
+use crate::input::{Action, KeyBindings};
+use crate::records::{RecordKind, Records};
+
+// Degrees per second the aim turns while an aim key is held
+const AIM_SPEED: f32 = 45f32;
+
+// A hand made board to clear with a limited number of launches.
+// Rows use `.` for a gap, R/M/S for regular, medium and strong blocks, B for a block that spawns a ball,
+// I for an indestructible block and > for a speed pad.
+pub struct Puzzle {
+    pub name: &'static str,
+    pub launches: u32,
+    // Launches a perfect clear takes
+    pub par: u32,
+    rows: &'static [&'static str],
+}
+
+impl Puzzle {
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn block_at(&self, x: usize, y: usize) -> Option<BlockType> {
+        match self.rows[y].as_bytes()[x] {
+            b'R' => Some(BlockType::Regular),
+            b'M' => Some(BlockType::Medium),
+            b'S' => Some(BlockType::Strong),
+            b'B' => Some(BlockType::SpawnBallOnDeath),
+            b'I' => Some(BlockType::Indestructible),
+            b'>' => Some(BlockType::SpeedPad),
+            _ => None,
+        }
+    }
+
+    // Three stars for clearing it in par, one less for every launch over it, but always one for clearing it
+    pub fn stars(&self, launches_used: u32) -> u32 {
+        3u32.saturating_sub(launches_used.saturating_sub(self.par)).max(1)
+    }
+}
+
+pub const PUZZLES: [Puzzle; 6] = [
+    Puzzle {
+        name: "First shot",
+        launches: 3,
+        par: 1,
+        rows: &[
+            "........",
+            "..RRRR..",
+            "..RRRR..",
+        ],
+    },
+    Puzzle {
+        name: "Gatekeeper",
+        launches: 4,
+        par: 2,
+        rows: &[
+            ".RRRRRR.",
+            ".RMMMMR.",
+            ".RRRRRR.",
+            "III..III",
+        ],
+    },
+    Puzzle {
+        name: "Split",
+        launches: 4,
+        par: 2,
+        rows: &[
+            "RR.II.RR",
+            "RR.II.RR",
+            "MM.II.MM",
+        ],
+    },
+    Puzzle {
+        name: "Chain reaction",
+        launches: 3,
+        par: 1,
+        rows: &[
+            "RRBRRBRR",
+            "RRRRRRRR",
+            "..R..R..",
+        ],
+    },
+    Puzzle {
+        name: "Speed trap",
+        launches: 5,
+        par: 3,
+        rows: &[
+            "SSSSSSSS",
+            "M>M>M>M>",
+            "RRRRRRRR",
+        ],
+    },
+    Puzzle {
+        name: "Fortress",
+        launches: 6,
+        par: 3,
+        rows: &[
+            "IRRRRRRI",
+            "IRSSSSRI",
+            "IRSBBSRI",
+            "IRRRRRRI",
+            "I......I",
+        ],
+    },
+];
+
+// The puzzle played as the given level, starting from 1
+pub fn puzzle(level: usize) -> &'static Puzzle {
+    &PUZZLES[(level.max(1) - 1) % PUZZLES.len()]
+}
+
+// Launches spent on the puzzle being played and where the next one is aimed
+#[derive(Default)]
+pub struct PuzzleRun {
+    pub launches_used: u32,
+    // Degrees from straight up
+    pub aim: f32,
+}
+
+impl PuzzleRun {
+    pub fn update_aim(&mut self, bindings: &KeyBindings, dt: f32) {
+        if bindings.is_down(Action::AimLeft) {
+            self.aim -= AIM_SPEED * dt;
+        }
+        if bindings.is_down(Action::AimRight) {
+            self.aim += AIM_SPEED * dt;
+        }
+        self.aim = self.aim.clamp(-LAUNCH_MAX_ANGLE, LAUNCH_MAX_ANGLE);
+    }
+
+    pub fn launches_left(&self, puzzle: &Puzzle) -> u32 {
+        puzzle.launches.saturating_sub(self.launches_used)
+    }
+}
+
+// Best star rating earned on every puzzle cleared so far
+pub type PuzzleStars = Records<usize, u32>;
+
+pub const PUZZLE_STARS: RecordKind<u32> = RecordKind {
+    storage_key: "puzzle_stars",
+    entry_name: "stars",
+    better: |stars, best| stars > best,
+};
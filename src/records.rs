@@ -0,0 +1,94 @@
+use crate::storage;
+
+// Keys and values of records, written to and read back from a `key,value` entry
+pub trait RecordField: Sized {
+    fn format(&self) -> String;
+    fn parse(text: &str) -> Option<Self>;
+}
+
+impl RecordField for usize {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
+}
+
+impl RecordField for u32 {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
+}
+
+impl RecordField for f32 {
+    fn format(&self) -> String {
+        self.to_string()
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        text.trim().parse().ok()
+    }
+}
+
+// Where a kind of record is saved and what makes a result beat the one on record
+pub struct RecordKind<V> {
+    pub storage_key: &'static str,
+    pub entry_name: &'static str,
+    pub better: fn(V, V) -> bool,
+}
+
+// The best result for every key (a level, or a level on a difficulty) played so far
+pub struct Records<K, V> {
+    kind: RecordKind<V>,
+    records: Vec<(K, V)>,
+}
+
+impl<K: RecordField + PartialEq + Copy, V: RecordField + Copy> Records<K, V> {
+    pub fn load(kind: RecordKind<V>) -> Self {
+        let records = storage::load(kind.storage_key)
+            .map(|text| {
+                storage::parse_entries(&text)
+                    .into_iter()
+                    .filter(|(name, _)| *name == kind.entry_name)
+                    .filter_map(|(_, value)| parse_record(value))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { kind, records }
+    }
+
+    pub fn save(&self) {
+        let text: String = self
+            .records
+            .iter()
+            .map(|(key, value)| format!("{}={},{}\n", self.kind.entry_name, key.format(), value.format()))
+            .collect();
+        storage::save(self.kind.storage_key, &text);
+    }
+
+    pub fn best(&self, key: K) -> Option<V> {
+        self.records.iter().find(|(other, _)| *other == key).map(|(_, value)| *value)
+    }
+
+    // Keeps the result if it beats the best one for the key, returning whether it did
+    pub fn record(&mut self, key: K, value: V) -> bool {
+        match self.records.iter_mut().find(|(other, _)| *other == key) {
+            Some((_, best)) if (self.kind.better)(value, *best) => *best = value,
+            Some(_) => return false,
+            None => self.records.push((key, value)),
+        }
+        true
+    }
+}
+
+// `key,value`, where the key may hold commas of its own
+fn parse_record<K: RecordField, V: RecordField>(text: &str) -> Option<(K, V)> {
+    let (key, value) = text.rsplit_once(',')?;
+    Some((K::parse(key)?, V::parse(value)?))
+}
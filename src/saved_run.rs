@@ -0,0 +1,150 @@
+use macroquad::prelude::*;
+use crate::game_objects::{
+    ball::Ball,
+    barrier::Barrier,
+    block::{Block, BlockType},
+    player::Player,
+};
+use crate::difficulty::Difficulty;
+use crate::session::SessionSnapshot;
+use crate::storage;
+
+const SAVED_RUN_KEY: &str = "run";
+
+// Timed powerup effects still running when the run was saved
+#[derive(Default)]
+pub struct ActivePowerups {
+    pub paddle_width: Option<f32>,
+    pub laser_timer: f32,
+    pub catch_timer: f32,
+    pub magnet_timer: f32,
+    pub fireball_timer: f32,
+    pub barrier_timer: Option<f32>,
+}
+
+// A single player run that can be continued from the title screen.
+// Block positions are stored relative to the screen size, so the board still fits if the window changed.
+pub struct SavedRun {
+    pub session: SessionSnapshot,
+    pub difficulty: Difficulty,
+    pub powerups: ActivePowerups,
+}
+
+impl SavedRun {
+    pub fn new(session: SessionSnapshot, difficulty: Difficulty, player: &Player, balls: &[Ball], barrier: &Option<Barrier>) -> Self {
+        Self {
+            session,
+            difficulty,
+            powerups: ActivePowerups {
+                paddle_width: Some(player.rect.w),
+                laser_timer: player.laser_timer,
+                catch_timer: player.catch_timer,
+                magnet_timer: player.magnet_timer,
+                fireball_timer: balls.iter().map(|ball| ball.fireball_timer).fold(0f32, f32::max),
+                barrier_timer: barrier.as_ref().map(|barrier| barrier.timer),
+            },
+        }
+    }
+
+    pub fn exists() -> bool {
+        storage::load(SAVED_RUN_KEY).is_some()
+    }
+
+    pub fn load() -> Option<Self> {
+        let text = storage::load(SAVED_RUN_KEY)?;
+        let mut session = SessionSnapshot::new(&[], 1, 0, 0);
+        let mut difficulty = None;
+        let mut powerups = ActivePowerups::default();
+        for (name, value) in storage::parse_entries(&text) {
+            match name {
+                "level" => session.level = value.parse().ok()?,
+                "score" => session.score = value.parse().ok()?,
+                "lives" => session.lives = value.parse().ok()?,
+                "difficulty" => difficulty = Some(Difficulty::parse(value)?),
+                "paddle_width" => powerups.paddle_width = value.parse().ok(),
+                "laser_timer" => powerups.laser_timer = value.parse().unwrap_or(0f32),
+                "catch_timer" => powerups.catch_timer = value.parse().unwrap_or(0f32),
+                "magnet_timer" => powerups.magnet_timer = value.parse().unwrap_or(0f32),
+                "fireball_timer" => powerups.fireball_timer = value.parse().unwrap_or(0f32),
+                "barrier_timer" => powerups.barrier_timer = value.parse().ok(),
+                "block" => session.blocks.push(parse_block(value)?),
+                _ => {}
+            }
+        }
+        let difficulty = difficulty?;
+        // A run without lives or blocks left is not worth continuing
+        if session.lives <= 0 || session.blocks.is_empty() {
+            return None;
+        }
+        Some(Self { session, difficulty, powerups })
+    }
+
+    pub fn save(&self) {
+        let mut text = format!(
+            "level={}\nscore={}\nlives={}\ndifficulty={}\nlaser_timer={}\ncatch_timer={}\nmagnet_timer={}\nfireball_timer={}\n",
+            self.session.level,
+            self.session.score,
+            self.session.lives,
+            self.difficulty.label(),
+            self.powerups.laser_timer,
+            self.powerups.catch_timer,
+            self.powerups.magnet_timer,
+            self.powerups.fireball_timer,
+        );
+        if let Some(width) = self.powerups.paddle_width {
+            text.push_str(&format!("paddle_width={}\n", width));
+        }
+        if let Some(timer) = self.powerups.barrier_timer {
+            text.push_str(&format!("barrier_timer={}\n", timer));
+        }
+        for block in self.session.blocks.iter() {
+            text.push_str(&format!(
+                "block={},{},{},{},{:?},{}\n",
+                block.rect.x / screen_width(),
+                block.rect.y / screen_height(),
+                block.rect.w / screen_width(),
+                block.rect.h / screen_height(),
+                block.block_type,
+                block.lives,
+            ));
+        }
+        storage::save(SAVED_RUN_KEY, &text);
+    }
+
+    pub fn delete() {
+        storage::remove(SAVED_RUN_KEY);
+    }
+
+    // Puts the saved powerup effects back on a freshly served paddle and ball
+    pub fn restore_powerups(&self, player: &mut Player, balls: &mut [Ball], barrier: &mut Option<Barrier>) {
+        if let Some(width) = self.powerups.paddle_width {
+            player.rect.w = width.min(screen_width() / 3.0).max(player.rect.w);
+        }
+        player.laser_timer = self.powerups.laser_timer;
+        player.catch_timer = self.powerups.catch_timer;
+        player.magnet_timer = self.powerups.magnet_timer;
+        for ball in balls.iter_mut() {
+            ball.fireball_timer = self.powerups.fireball_timer;
+        }
+        *barrier = self.powerups.barrier_timer.map(|timer| {
+            let mut restored = Barrier::new(player.facing);
+            restored.timer = timer;
+            restored
+        });
+    }
+}
+
+// `x,y,w,h,type,lives` with the rect relative to the screen size
+fn parse_block(value: &str) -> Option<Block> {
+    let fields: Vec<&str> = value.split(',').map(|field| field.trim()).collect();
+    if fields.len() != 6 {
+        return None;
+    }
+    let rect_field = |index: usize, scale: f32| fields[index].parse::<f32>().ok().map(|v| v * scale);
+    let pos = vec2(rect_field(0, screen_width())?, rect_field(1, screen_height())?);
+    let size = vec2(rect_field(2, screen_width())?, rect_field(3, screen_height())?);
+    let mut block = Block::new(pos, BlockType::parse(fields[4])?, size);
+    // Never more lives than the block type starts with, which is all its colors cover
+    block.lives = fields[5].parse::<i32>().ok()?.clamp(1, block.lives);
+    Some(block)
+}
@@ -0,0 +1,168 @@
+use crate::game_objects::block::BlockType;
+
+// Points for a hit that damages a block without destroying it
+pub const CHIP_POINTS: i32 = 5;
+// Bonus for clearing a level without losing a single ball
+pub const NO_MISS_BONUS: i32 = 500;
+// Bonus per second a level is cleared under its par time
+const CLEAR_TIME_BONUS: f32 = 10f32;
+// Block hits in a row it takes to raise the combo multiplier by one
+const COMBO_STEP: u32 = 5;
+const MAX_MULTIPLIER: i32 = 5;
+// Seconds the HUD flashes after an extra life is awarded
+pub const EXTRA_LIFE_FLASH: f32 = 2f32;
+
+// Points for destroying a block, tougher blocks are worth more
+pub fn block_points(block_type: BlockType) -> i32 {
+    match block_type {
+        BlockType::Regular => 10,
+        BlockType::SpawnBallOnDeath | BlockType::SpawnPowerup => 20,
+        BlockType::Medium => 25,
+        BlockType::Strong => 40,
+        BlockType::Indestructible | BlockType::SpeedPad => 0,
+    }
+}
+
+// Tally of the level being played: block points, the combo of block hits since a paddle last
+// touched a ball, and the balls lost along the way
+#[derive(Default)]
+pub struct LevelScore {
+    block_points: i32,
+    combo_points: i32,
+    combo: u32,
+    best_multiplier: i32,
+    balls_lost: u32,
+}
+
+impl LevelScore {
+    pub fn multiplier(&self) -> i32 {
+        (1 + (self.combo / COMBO_STEP) as i32).min(MAX_MULTIPLIER)
+    }
+
+    // Scores a block hit worth `points` and returns what it is worth with the combo multiplier
+    pub fn block_hit(&mut self, points: i32) -> i32 {
+        if points == 0 {
+            return 0;
+        }
+        let multiplier = self.multiplier();
+        self.block_points += points;
+        self.combo_points += points * (multiplier - 1);
+        self.best_multiplier = self.best_multiplier.max(multiplier);
+        self.combo += 1;
+        points * multiplier
+    }
+
+    pub fn paddle_hit(&mut self) {
+        self.combo = 0;
+    }
+
+    pub fn ball_lost(&mut self) {
+        self.combo = 0;
+        self.balls_lost += 1;
+    }
+
+    pub fn is_flawless(&self) -> bool {
+        self.balls_lost == 0
+    }
+
+    pub fn breakdown(&self, time: f32, par_time: f32) -> ScoreBreakdown {
+        ScoreBreakdown {
+            blocks: self.block_points,
+            combo: self.combo_points,
+            best_multiplier: self.best_multiplier.max(1),
+            clear_time: ((par_time - time).max(0f32) * CLEAR_TIME_BONUS) as i32,
+            no_miss: if self.is_flawless() { NO_MISS_BONUS } else { 0 },
+        }
+    }
+}
+
+// Where a cleared level's points came from, for the level end screen
+pub struct ScoreBreakdown {
+    pub blocks: i32,
+    pub combo: i32,
+    pub best_multiplier: i32,
+    pub clear_time: i32,
+    pub no_miss: i32,
+}
+
+impl ScoreBreakdown {
+    // The bonuses still to be added when the level ends, blocks and combos were scored during play
+    pub fn bonus(&self) -> i32 {
+        self.clear_time + self.no_miss
+    }
+
+    pub fn total(&self) -> i32 {
+        self.blocks + self.combo + self.bonus()
+    }
+}
+
+// Scores that award an extra life, arcade style: the first one, then one more every `every` points after it.
+// A threshold of 0 turns that part off.
+#[derive(Clone, Copy)]
+pub struct ExtraLives {
+    pub first: i32,
+    pub every: i32,
+}
+
+impl ExtraLives {
+    pub const DEFAULT: ExtraLives = ExtraLives { first: 5000, every: 15000 };
+
+    // Extra lives awarded for getting from `before` to `after` points
+    pub fn earned(&self, before: i32, after: i32) -> i32 {
+        (self.awarded_at(after) - self.awarded_at(before)).max(0)
+    }
+
+    // Extra lives a score has awarded in total
+    fn awarded_at(&self, score: i32) -> i32 {
+        if self.first <= 0 || score < self.first {
+            0
+        } else if self.every <= 0 {
+            1
+        } else {
+            1 + (score - self.first) / self.every
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_life_on_reaching_the_first_threshold() {
+        let lives = ExtraLives { first: 5000, every: 15000 };
+        assert_eq!(lives.earned(0, 4999), 0);
+        assert_eq!(lives.earned(4999, 5000), 1);
+        assert_eq!(lives.earned(5000, 5100), 0);
+    }
+
+    #[test]
+    fn one_extra_life_per_threshold_crossed() {
+        let lives = ExtraLives { first: 5000, every: 15000 };
+        assert_eq!(lives.earned(19999, 20000), 1);
+        assert_eq!(lives.earned(0, 35000), 3);
+        assert_eq!(lives.earned(4000, 34999), 2);
+    }
+
+    #[test]
+    fn every_of_zero_only_awards_the_first() {
+        let lives = ExtraLives { first: 5000, every: 0 };
+        assert_eq!(lives.earned(0, 100000), 1);
+        assert_eq!(lives.earned(5000, 100000), 0);
+    }
+
+    #[test]
+    fn first_of_zero_turns_extra_lives_off() {
+        let lives = ExtraLives { first: 0, every: 15000 };
+        assert_eq!(lives.earned(0, 100000), 0);
+    }
+
+    #[test]
+    fn penalties_never_take_lives_back() {
+        let lives = ExtraLives::DEFAULT;
+        assert_eq!(lives.earned(20000, 4000), 0);
+        assert_eq!(lives.earned(100, -50), 0);
+        assert_eq!(lives.earned(-500, 4999), 0);
+        assert_eq!(lives.earned(-500, 5000), 1);
+    }
+}
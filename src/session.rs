@@ -0,0 +1,50 @@
+use crate::game_objects::block::Block;
+
+// Everything that belongs to one player's run, so it can be parked while someone else plays
+#[derive(Clone)]
+pub struct SessionSnapshot {
+    pub blocks: Vec<Block>,
+    pub level: usize,
+    pub score: i32,
+    pub lives: i32,
+}
+
+impl SessionSnapshot {
+    pub fn new(blocks: &[Block], level: usize, score: i32, lives: i32) -> Self {
+        Self {
+            blocks: blocks.to_vec(),
+            level,
+            score,
+            lives,
+        }
+    }
+}
+
+// Players taking turns on the same paddle, arcade style.
+// The active player's session is live in the game loop, the others wait here.
+pub struct HotSeat {
+    pub turn: usize,
+    pub sessions: Vec<SessionSnapshot>,
+}
+
+impl HotSeat {
+    // Everyone starts from the same board
+    pub fn new(player_count: usize, start: &SessionSnapshot) -> Self {
+        Self {
+            turn: 0,
+            sessions: vec![start.clone(); player_count],
+        }
+    }
+
+    // Parks the active player's session and hands the turn to the next player with lives left,
+    // returning the session to continue with. None once everyone is out of lives.
+    pub fn swap_turn(&mut self, current: SessionSnapshot) -> Option<SessionSnapshot> {
+        self.sessions[self.turn] = current;
+        let player_count = self.sessions.len();
+        let next = (1..=player_count)
+            .map(|offset| (self.turn + offset) % player_count)
+            .find(|player| self.sessions[*player].lives > 0)?;
+        self.turn = next;
+        Some(self.sessions[next].clone())
+    }
+}
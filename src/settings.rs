@@ -0,0 +1,88 @@
+use crate::difficulty::Difficulty;
+use crate::game_objects::{audio_manager::AudioManager, player::POINTER_MAX_SPEED, texture_manager::TextureManager};
+use crate::input::KeyBindings;
+use crate::input_source::PaddleControl;
+use crate::scoring::ExtraLives;
+use crate::storage;
+
+const SETTINGS_KEY: &str = "settings";
+
+pub struct Settings {
+    pub key_bindings: KeyBindings,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub difficulty: Difficulty,
+    pub paddle_control: PaddleControl,
+    pub fullscreen: bool,
+    pub colorblind_mode: bool,
+    // Only set in the settings file, there is no option for it
+    pub extra_lives: ExtraLives,
+    // Fastest the paddle may chase the mouse or a touch, also only set in the settings file
+    pub pointer_max_speed: f32,
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self {
+            key_bindings: KeyBindings::new(),
+            music_volume: 0.5,
+            sfx_volume: 1.0,
+            difficulty: Difficulty::Normal,
+            paddle_control: PaddleControl::Auto,
+            fullscreen: false,
+            colorblind_mode: false,
+            extra_lives: ExtraLives::DEFAULT,
+            pointer_max_speed: POINTER_MAX_SPEED,
+        }
+    }
+
+    // Loads the saved settings, falling back to defaults for anything missing
+    pub fn load() -> Self {
+        let mut settings = Self::new();
+        if let Some(text) = storage::load(SETTINGS_KEY) {
+            for (name, value) in storage::parse_entries(&text) {
+                settings.apply_setting(name, value);
+            }
+        }
+        settings
+    }
+
+    fn apply_setting(&mut self, name: &str, value: &str) {
+        match name {
+            "music_volume" => self.music_volume = value.parse::<f32>().map_or(self.music_volume, |volume| volume.clamp(0.0, 1.0)),
+            "sfx_volume" => self.sfx_volume = value.parse::<f32>().map_or(self.sfx_volume, |volume| volume.clamp(0.0, 1.0)),
+            "difficulty" => self.difficulty = Difficulty::parse(value).unwrap_or(self.difficulty),
+            "paddle_control" => self.paddle_control = PaddleControl::parse(value).unwrap_or(self.paddle_control),
+            "fullscreen" => self.fullscreen = value == "true",
+            "colorblind_mode" => self.colorblind_mode = value == "true",
+            "extra_life_first" => self.extra_lives.first = value.parse().unwrap_or(self.extra_lives.first),
+            "extra_life_every" => self.extra_lives.every = value.parse().unwrap_or(self.extra_lives.every),
+            "pointer_max_speed" => self.pointer_max_speed = value.parse::<f32>().map_or(self.pointer_max_speed, |speed| speed.max(1.0)),
+            _ => self.key_bindings.apply_setting(name, value),
+        }
+    }
+
+    pub fn save(&self) {
+        let text = format!(
+            "music_volume={}\nsfx_volume={}\ndifficulty={}\npaddle_control={}\nfullscreen={}\ncolorblind_mode={}\nextra_life_first={}\nextra_life_every={}\npointer_max_speed={}\n{}",
+            self.music_volume,
+            self.sfx_volume,
+            self.difficulty.label(),
+            self.paddle_control.label(),
+            self.fullscreen,
+            self.colorblind_mode,
+            self.extra_lives.first,
+            self.extra_lives.every,
+            self.pointer_max_speed,
+            self.key_bindings.to_settings(),
+        );
+        storage::save(SETTINGS_KEY, &text);
+    }
+
+    // Pushes the audio and drawing settings to the managers that use them
+    pub fn apply(&self, audio_manager: &mut AudioManager, texture_manager: &mut TextureManager) {
+        audio_manager.sfx_volume = self.sfx_volume;
+        audio_manager.set_music_volume(self.music_volume);
+        texture_manager.colorblind_mode = self.colorblind_mode;
+    }
+}
@@ -0,0 +1,189 @@
+use macroquad::prelude::*;
+use crate::events::GameEvent;
+use crate::game_objects::block::BlockType;
+use crate::storage;
+
+const STATISTICS_KEY: &str = "statistics";
+
+// What the stats screens call each kind of block
+fn block_label(block_type: BlockType) -> &'static str {
+    match block_type {
+        BlockType::Regular => "Regular blocks",
+        BlockType::Medium => "Medium blocks",
+        BlockType::Strong => "Strong blocks",
+        BlockType::SpawnBallOnDeath => "Multiball blocks",
+        BlockType::SpawnPowerup => "Powerup blocks",
+        BlockType::Indestructible => "Indestructible blocks",
+        BlockType::SpeedPad => "Speed pads",
+    }
+}
+
+// h:mm:ss
+fn format_play_time(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+// Counts over some stretch of play, a single run or every run ever
+#[derive(Default)]
+pub struct Stats {
+    // Indexed like BlockType::ALL
+    blocks_destroyed: [u32; BlockType::ALL.len()],
+    balls_lost: u32,
+    paddle_hits: u32,
+    pub powerups_collected: u32,
+    // Most paddle hits in a row without losing a ball
+    longest_rally: u32,
+    play_time: f32,
+    pub levels_cleared: u32,
+    rally: u32,
+}
+
+impl Stats {
+    pub fn total_blocks_destroyed(&self) -> u32 {
+        self.blocks_destroyed.iter().sum()
+    }
+
+    fn record(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::BlockDestroyed(block_type) => {
+                if let Some(index) = BlockType::ALL.iter().position(|other| other == block_type) {
+                    self.blocks_destroyed[index] += 1;
+                }
+            }
+            GameEvent::PaddleHit => {
+                self.paddle_hits += 1;
+                self.rally += 1;
+                self.longest_rally = self.longest_rally.max(self.rally);
+            }
+            GameEvent::BallLost => {
+                self.balls_lost += 1;
+                self.rally = 0;
+            }
+            GameEvent::PowerupCollected => self.powerups_collected += 1,
+            GameEvent::LevelCleared { .. } => self.levels_cleared += 1,
+            _ => {}
+        }
+    }
+
+    // Label and value of every stat worth showing, blocks only for the kinds that can be destroyed
+    fn lines(&self) -> Vec<(String, String)> {
+        let mut lines: Vec<(String, String)> = BlockType::ALL
+            .iter()
+            .zip(self.blocks_destroyed)
+            .filter(|(block_type, _)| !matches!(block_type, BlockType::Indestructible | BlockType::SpeedPad))
+            .map(|(block_type, count)| (format!("{} destroyed", block_label(*block_type)), count.to_string()))
+            .collect();
+        lines.extend([
+            ("Balls lost".to_string(), self.balls_lost.to_string()),
+            ("Paddle hits".to_string(), self.paddle_hits.to_string()),
+            ("Powerups collected".to_string(), self.powerups_collected.to_string()),
+            ("Longest rally".to_string(), self.longest_rally.to_string()),
+            ("Play time".to_string(), format_play_time(self.play_time)),
+            ("Levels cleared".to_string(), self.levels_cleared.to_string()),
+        ]);
+        lines
+    }
+}
+
+// Lifetime statistics, kept across sessions, next to the ones of the run in progress
+pub struct Statistics {
+    pub lifetime: Stats,
+    pub run: Stats,
+}
+
+impl Statistics {
+    pub fn load() -> Self {
+        let mut lifetime = Stats::default();
+        if let Some(text) = storage::load(STATISTICS_KEY) {
+            for (name, value) in storage::parse_entries(&text) {
+                if let Some(block_name) = name.strip_prefix("blocks.") {
+                    if let Some(index) = BlockType::parse(block_name).and_then(|block_type| BlockType::ALL.iter().position(|other| *other == block_type)) {
+                        lifetime.blocks_destroyed[index] = value.parse().unwrap_or(0);
+                    }
+                    continue;
+                }
+                match name {
+                    "balls_lost" => lifetime.balls_lost = value.parse().unwrap_or(0),
+                    "paddle_hits" => lifetime.paddle_hits = value.parse().unwrap_or(0),
+                    "powerups_collected" => lifetime.powerups_collected = value.parse().unwrap_or(0),
+                    "longest_rally" => lifetime.longest_rally = value.parse().unwrap_or(0),
+                    "play_time" => lifetime.play_time = value.parse().unwrap_or(0f32),
+                    "levels_cleared" => lifetime.levels_cleared = value.parse().unwrap_or(0),
+                    _ => {}
+                }
+            }
+        }
+        Self { lifetime, run: Stats::default() }
+    }
+
+    pub fn save(&self) {
+        let lifetime = &self.lifetime;
+        let mut text: String = BlockType::ALL
+            .iter()
+            .zip(lifetime.blocks_destroyed)
+            .map(|(block_type, count)| format!("blocks.{:?}={}\n", block_type, count))
+            .collect();
+        text.push_str(&format!(
+            "balls_lost={}\npaddle_hits={}\npowerups_collected={}\nlongest_rally={}\nplay_time={}\nlevels_cleared={}\n",
+            lifetime.balls_lost,
+            lifetime.paddle_hits,
+            lifetime.powerups_collected,
+            lifetime.longest_rally,
+            lifetime.play_time,
+            lifetime.levels_cleared,
+        ));
+        storage::save(STATISTICS_KEY, &text);
+    }
+
+    pub fn start_run(&mut self) {
+        self.run = Stats::default();
+    }
+
+    // Counts the event for the run and for the lifetime totals.
+    // Returns true at the natural breaks in play, when the totals are worth saving.
+    pub fn handle(&mut self, event: &GameEvent) -> bool {
+        self.run.record(event);
+        self.lifetime.record(event);
+        matches!(event, GameEvent::LevelCleared { .. } | GameEvent::BallLost)
+    }
+
+    pub fn add_play_time(&mut self, dt: f32) {
+        self.run.play_time += dt;
+        self.lifetime.play_time += dt;
+    }
+
+    pub fn draw(&self, font: &Font) {
+        let title = "Statistics";
+        let title_dims = measure_text(title, Some(font), 50u16, 1.0f32);
+        draw_text_ex(
+            title,
+            screen_width() * 0.5f32 - title_dims.width * 0.5f32,
+            screen_height() * 0.15f32,
+            TextParams { font: Some(font), font_size: 50u16, color: BLACK, ..Default::default() },
+        );
+        draw_stat_lines(&self.lifetime.lines(), screen_height() * 0.25f32, screen_height() * 0.65f32, font);
+    }
+
+    // What the run that just ended added up to, under the game over text
+    pub fn draw_run_summary(&self, font: &Font) {
+        draw_stat_lines(&self.run.lines(), screen_height() * 0.5f32 + 50f32, screen_height() * 0.45f32 - 60f32, font);
+    }
+}
+
+// Two columns of labels and values from `top`, shrunk to fit in `height`
+fn draw_stat_lines(lines: &[(String, String)], top: f32, height: f32, font: &Font) {
+    let row_height = (height / lines.len().max(1) as f32).min(40f32);
+    let font_size = (row_height * 0.7f32) as u16;
+    for (i, (label, value)) in lines.iter().enumerate() {
+        let y = top + i as f32 * row_height;
+        for (cell, column) in [(label, 0.25f32), (value, 0.65f32)] {
+            draw_text_ex(
+                cell,
+                screen_width() * column,
+                y,
+                TextParams { font: Some(font), font_size, color: BLACK, ..Default::default() },
+            );
+        }
+    }
+}
@@ -0,0 +1,63 @@
+// Persists small text blobs between runs.
+// Native builds write files into a save directory next to the game, the wasm build uses the
+// browser's localStorage through the storage plugin registered in index.html.
+
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_DIR: &str = "save";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load(key: &str) -> Option<String> {
+    std::fs::read_to_string(format!("{}/{}.txt", SAVE_DIR, key)).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save(key: &str, value: &str) {
+    if let Err(err) = std::fs::create_dir_all(SAVE_DIR)
+        .and_then(|_| std::fs::write(format!("{}/{}.txt", SAVE_DIR, key), value))
+    {
+        eprintln!("Failed to save {}: {}", key, err);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn remove(key: &str) {
+    let _ = std::fs::remove_file(format!("{}/{}.txt", SAVE_DIR, key));
+}
+
+#[cfg(target_arch = "wasm32")]
+unsafe extern "C" {
+    fn rustanoid_storage_load(key_ptr: *const u8, key_len: u32) -> i32;
+    fn rustanoid_storage_read(buf_ptr: *mut u8);
+    fn rustanoid_storage_save(key_ptr: *const u8, key_len: u32, value_ptr: *const u8, value_len: u32);
+    fn rustanoid_storage_remove(key_ptr: *const u8, key_len: u32);
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn load(key: &str) -> Option<String> {
+    // The plugin stages the value on the JS side and reports its length, then copies it into our buffer
+    let len = unsafe { rustanoid_storage_load(key.as_ptr(), key.len() as u32) };
+    if len < 0 {
+        return None;
+    }
+    let mut buf = vec![0u8; len as usize];
+    unsafe { rustanoid_storage_read(buf.as_mut_ptr()) };
+    String::from_utf8(buf).ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save(key: &str, value: &str) {
+    unsafe { rustanoid_storage_save(key.as_ptr(), key.len() as u32, value.as_ptr(), value.len() as u32) };
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn remove(key: &str) {
+    unsafe { rustanoid_storage_remove(key.as_ptr(), key.len() as u32) };
+}
+
+// Parses the simple `key=value` line format used by all save data
+pub fn parse_entries(text: &str) -> Vec<(&str, &str)> {
+    text.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect()
+}
@@ -0,0 +1,70 @@
+use macroquad::prelude::*;
+use crate::game_objects::block::{Block, BlockType};
+
+// Blocks across each generated row
+pub const SURVIVAL_COLUMNS: usize = 10;
+// Rows already on the board when a survival run starts
+pub const SURVIVAL_START_ROWS: usize = 4;
+// Seconds the board takes to creep down one row, shrinking as more rows come in
+const FIRST_ROW_INTERVAL: f32 = 12f32;
+const MIN_ROW_INTERVAL: f32 = 4f32;
+const ROW_INTERVAL_STEP: f32 = 0.25f32;
+
+// The creeping board of an endless survival run
+#[derive(Default)]
+pub struct Survival {
+    pub rows_added: usize,
+    // How far the board has crept since the last row came in
+    offset: f32,
+}
+
+impl Survival {
+    fn row_interval(&self) -> f32 {
+        (FIRST_ROW_INTERVAL - self.rows_added as f32 * ROW_INTERVAL_STEP).max(MIN_ROW_INTERVAL)
+    }
+
+    // Moves the blocks down by their share of a row and returns where a new row should come in at the
+    // top, once the board has crept a whole row. An empty board gets its next row straight away.
+    pub fn update(&mut self, dt: f32, row_pitch: f32, top: f32, blocks: &mut [Block]) -> Option<f32> {
+        let step = row_pitch * dt / self.row_interval();
+        for block in blocks.iter_mut() {
+            block.rect.y += step;
+        }
+        self.offset += step;
+
+        if self.offset >= row_pitch {
+            self.offset -= row_pitch;
+        } else if blocks.iter().any(|block| block.lives > 0) {
+            return None;
+        } else {
+            self.offset = 0f32;
+        }
+        self.rows_added += 1;
+        Some(top + self.offset)
+    }
+}
+
+// A row of random blocks with the odd gap, getting tougher the more rows came before it
+pub fn generate_row(start_x: f32, y: f32, block_size: Vec2, padding: f32, depth: usize) -> Vec<Block> {
+    let tough_chance = (0.1f32 + depth as f32 * 0.01f32).min(0.5f32);
+    (0..SURVIVAL_COLUMNS)
+        .filter_map(|column| {
+            let roll = rand::gen_range(0f32, 1f32);
+            let block_type = if roll < 0.1f32 {
+                return None;
+            } else if roll < 0.13f32 {
+                BlockType::SpawnPowerup
+            } else if roll < 0.15f32 {
+                BlockType::SpawnBallOnDeath
+            } else if roll < 0.15f32 + tough_chance * 0.4f32 {
+                BlockType::Strong
+            } else if roll < 0.15f32 + tough_chance {
+                BlockType::Medium
+            } else {
+                BlockType::Regular
+            };
+            let x = start_x + column as f32 * (block_size.x + padding);
+            Some(Block::new(vec2(x, y), block_type, block_size))
+        })
+        .collect()
+}
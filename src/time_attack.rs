@@ -0,0 +1,77 @@
+use crate::difficulty::Difficulty;
+use crate::records::{RecordField, RecordKind, Records};
+
+// Seconds added to the clock in time attack whenever the last ball in play is lost
+pub const LOST_BALL_PENALTY: f32 = 5f32;
+
+// Target times for a level, part of its layout
+#[derive(Clone, Copy, Default)]
+pub struct ParTimes {
+    pub par: f32,
+    pub gold: f32,
+}
+
+impl ParTimes {
+    pub const fn new(par: f32, gold: f32) -> Self {
+        Self { par, gold }
+    }
+
+    // How a finishing time measures up against the targets
+    pub fn rating(&self, time: f32) -> &'static str {
+        if time <= self.gold {
+            "Gold"
+        } else if time <= self.par {
+            "Under par"
+        } else {
+            "Over par"
+        }
+    }
+}
+
+// The clock of the level being played, lost balls add to it on top of the time actually taken
+#[derive(Default)]
+pub struct LevelTimer {
+    pub elapsed: f32,
+    pub penalty: f32,
+}
+
+impl LevelTimer {
+    pub fn update(&mut self, dt: f32) {
+        self.elapsed += dt;
+    }
+
+    pub fn add_penalty(&mut self) {
+        self.penalty += LOST_BALL_PENALTY;
+    }
+
+    pub fn total(&self) -> f32 {
+        self.elapsed + self.penalty
+    }
+}
+
+// Fastest time attack clear of every level played so far, on each difficulty since they play so differently
+pub type BestTimes = Records<(Difficulty, usize), f32>;
+
+pub const BEST_TIMES: RecordKind<f32> = RecordKind {
+    storage_key: "best_times",
+    entry_name: "time",
+    better: |time, best| time < best,
+};
+
+// `difficulty,level`
+impl RecordField for (Difficulty, usize) {
+    fn format(&self) -> String {
+        format!("{},{}", self.0.label(), self.1)
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let (difficulty, level) = text.split_once(',')?;
+        Some((Difficulty::parse(difficulty.trim())?, level.trim().parse().ok()?))
+    }
+}
+
+// m:ss.t
+pub fn format_time(seconds: f32) -> String {
+    let tenths = (seconds * 10f32).floor() as u32;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}